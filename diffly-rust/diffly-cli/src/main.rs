@@ -1,11 +1,22 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs::File;
 use std::io::{self, Write};
 use std::path::Path;
+use std::rc::Rc;
 
-use diffly_core::{DiffOptions, HeaderMode};
+use diffly_core::{
+    ColumnType, CsvTrim, DiffOptions, FieldDiffMode, FloatTolerance, HeaderMode, JoinSelection,
+    OutputMode, TimestampGranularity,
+};
 use diffly_engine::{
-    run_keyed_to_sink_with_config, EngineError, EngineRunConfig, EventSink, NeverCancel,
+    partition_inputs3_to_spill, partition_inputs_to_spill_for_join,
+    partition_sources_to_spill_with_store,
+    run_join_to_sink, run_keyed_to_sink_with_config, run_merge_to_sink,
+    run_partitioned_manifest_to_sink, AutoMergeSolver, CsvRecordSource, EngineError,
+    EngineRunConfig, EventSink, JoinMode, MergeStrategy, NdjsonRecordSource, NeverCancel,
+    ParquetRecordSource, RecordSource, SpillStore, TeeSink, TempDirSpill,
 };
 use serde_json::{json, Value};
 
@@ -14,6 +25,7 @@ enum OutputFormat {
     Jsonl,
     Json,
     Summary,
+    Csv,
 }
 
 impl OutputFormat {
@@ -22,11 +34,183 @@ impl OutputFormat {
             "jsonl" => Ok(Self::Jsonl),
             "json" => Ok(Self::Json),
             "summary" => Ok(Self::Summary),
+            "csv" => Ok(Self::Csv),
             _ => Err(format!("Unsupported --format value: {value}")),
         }
     }
 }
 
+/// Which difference classes make `--exit-code` report failure.
+#[derive(Clone, Copy)]
+enum FailOn {
+    Any,
+    Changed,
+    Added,
+    Removed,
+}
+
+impl FailOn {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "any" => Ok(Self::Any),
+            "changed" => Ok(Self::Changed),
+            "added" => Ok(Self::Added),
+            "removed" => Ok(Self::Removed),
+            _ => Err(format!("Unsupported --fail-on value: {value}")),
+        }
+    }
+}
+
+/// Exit code used by `--exit-code` when differences matching `--fail-on`
+/// are found. Distinct from the generic error code (`2`) so CI pipelines
+/// can tell "diffly itself failed" apart from "diffly found differences".
+const DIFFERENCES_FOUND_EXIT_CODE: i32 = 1;
+
+/// CLI-facing mirror of `diffly_engine::MergeStrategy`, selected via
+/// `--merge-strategy`. Only meaningful when `--partitions` is also set.
+#[derive(Clone, Copy)]
+enum MergeStrategyArg {
+    Hashed,
+    Sorted,
+}
+
+impl MergeStrategyArg {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "hashed" => Ok(Self::Hashed),
+            "sorted" => Ok(Self::Sorted),
+            _ => Err(format!("Unsupported --merge-strategy value: {value}")),
+        }
+    }
+
+    fn into_engine(self) -> MergeStrategy {
+        match self {
+            Self::Hashed => MergeStrategy::Hashed,
+            Self::Sorted => MergeStrategy::Sorted,
+        }
+    }
+}
+
+/// CLI-facing mirror of `diffly_engine::JoinMode`, selected via
+/// `--join-mode`. Setting this switches the run from a diff to a
+/// side-by-side join over the partitioned engine path.
+#[derive(Clone, Copy)]
+enum JoinModeArg {
+    Inner,
+    LeftOuter,
+    RightOuter,
+    FullOuter,
+    LeftAnti,
+    RightAnti,
+}
+
+impl JoinModeArg {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "inner" => Ok(Self::Inner),
+            "left" => Ok(Self::LeftOuter),
+            "right" => Ok(Self::RightOuter),
+            "full" => Ok(Self::FullOuter),
+            "left-anti" => Ok(Self::LeftAnti),
+            "right-anti" => Ok(Self::RightAnti),
+            _ => Err(format!("Unsupported --join-mode value: {value}")),
+        }
+    }
+
+    fn into_engine(self) -> JoinMode {
+        match self {
+            Self::Inner => JoinMode::Inner,
+            Self::LeftOuter => JoinMode::LeftOuter,
+            Self::RightOuter => JoinMode::RightOuter,
+            Self::FullOuter => JoinMode::FullOuter,
+            Self::LeftAnti => JoinMode::LeftAnti,
+            Self::RightAnti => JoinMode::RightAnti,
+        }
+    }
+}
+
+/// Partition count used for `--join-mode` when `--partitions` isn't given
+/// explicitly; joins always run over the partitioned engine path.
+const DEFAULT_JOIN_PARTITIONS: usize = 16;
+
+/// Record format for `--a-format`/`--b-format`. Defaults to `Csv`; setting
+/// either to `Ndjson`/`Parquet` routes the run through
+/// `partition_sources_to_spill_with_store` instead of the CSV-only
+/// `diff_csv_files`/`partition_inputs_to_spill` paths, so A and B no longer
+/// have to share a file format.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InputFormat {
+    Csv,
+    Ndjson,
+    Parquet,
+}
+
+impl InputFormat {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "csv" => Ok(Self::Csv),
+            "ndjson" => Ok(Self::Ndjson),
+            "parquet" => Ok(Self::Parquet),
+            _ => Err(format!("Unsupported --a-format/--b-format value: {value}")),
+        }
+    }
+
+    fn open(self, path: &Path, label: &str, options: &DiffOptions) -> Result<Box<dyn RecordSource>, EngineError> {
+        match self {
+            Self::Csv => Ok(Box::new(CsvRecordSource::open(path, label, options)?)),
+            Self::Ndjson => Ok(Box::new(NdjsonRecordSource::open(path, label)?)),
+            Self::Parquet => Ok(Box::new(ParquetRecordSource::open(path, label)?)),
+        }
+    }
+}
+
+/// Tallies row-level difference counts so `--exit-code` can be evaluated
+/// both from a buffered event list and from a streaming sink.
+#[derive(Default)]
+struct DiffCounts {
+    added: u64,
+    removed: u64,
+    changed: u64,
+}
+
+impl DiffCounts {
+    fn observe(&mut self, event_type: &str) {
+        match event_type {
+            "added" => self.added += 1,
+            "removed" => self.removed += 1,
+            "changed" => self.changed += 1,
+            _ => {}
+        }
+    }
+
+    fn has_failures(&self, fail_on: FailOn) -> bool {
+        match fail_on {
+            FailOn::Any => self.added > 0 || self.removed > 0 || self.changed > 0,
+            FailOn::Added => self.added > 0,
+            FailOn::Removed => self.removed > 0,
+            FailOn::Changed => self.changed > 0,
+        }
+    }
+
+    fn from_stats_events(events: &[Value]) -> Self {
+        let stats = stats_from_events(events);
+        Self {
+            added: stats
+                .and_then(|v| v.get("rows_added"))
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+            removed: stats
+                .and_then(|v| v.get("rows_removed"))
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+            changed: stats
+                .and_then(|v| v.get("rows_changed"))
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+        }
+    }
+}
+
 struct CliArgs {
     a_path: String,
     b_path: String,
@@ -36,10 +220,35 @@ struct CliArgs {
     emit_progress: bool,
     partition_count: Option<usize>,
     disable_partitions: bool,
+    merge_strategy: MergeStrategyArg,
+    partition_memory_budget: Option<usize>,
+    join_mode: Option<JoinModeArg>,
+    column_types: HashMap<String, ColumnType>,
+    float_tolerance: HashMap<String, FloatTolerance>,
+    timestamp_granularity: HashMap<String, TimestampGranularity>,
+    base_path: Option<String>,
+    field_diff: FieldDiffMode,
+    field_diff_max_len: usize,
+    a_format: InputFormat,
+    b_format: InputFormat,
     output_format: OutputFormat,
     output_path: Option<String>,
     pretty: bool,
     ignore_column_order: bool,
+    delimiter: u8,
+    quote: u8,
+    trim: CsvTrim,
+    flexible: bool,
+    lossless_bytes: bool,
+    jobs: usize,
+    drop_equal_fields: bool,
+    ignore_row_order: bool,
+    streaming: bool,
+    output_mode: OutputMode,
+    join_selection: JoinSelection,
+    exit_code: bool,
+    fail_on: FailOn,
+    tee_targets: Vec<(OutputFormat, Option<String>)>,
 }
 
 fn parse_key_csv(value: &str) -> Vec<String> {
@@ -51,19 +260,169 @@ fn parse_key_csv(value: &str) -> Vec<String> {
         .collect()
 }
 
+/// Parses one repeated `--out <format>=<path>` tee target. `path` of `-`
+/// means stdout. Returns `None` when `value` doesn't have the
+/// `format=path` shape, so callers can fall back to treating it as a
+/// plain single output path.
+fn parse_tee_target(value: &str) -> Option<(OutputFormat, Option<String>)> {
+    let (format_part, path_part) = value.split_once('=')?;
+    let format = OutputFormat::parse(format_part).ok()?;
+    let path = if path_part == "-" {
+        None
+    } else {
+        Some(path_part.to_string())
+    };
+    Some((format, path))
+}
+
+/// Splits a repeated `--column-type`/`--timestamp-granularity`-style
+/// `<column>=<value>` flag argument into its two halves.
+fn parse_column_pair<'a>(flag: &str, value: &'a str) -> Result<(&'a str, &'a str), String> {
+    value
+        .split_once('=')
+        .ok_or_else(|| format!("{flag} requires a value in <column>=<value> form, got: {value}"))
+}
+
+/// Parses `--float-tolerance <column>=<absolute>[,<relative>]`.
+fn parse_float_tolerance(value: &str) -> Result<(String, FloatTolerance), String> {
+    let (column, rest) = parse_column_pair("--float-tolerance", value)?;
+    let mut parts = rest.splitn(2, ',');
+    let absolute = parts
+        .next()
+        .unwrap()
+        .parse::<f64>()
+        .map_err(|_| format!("--float-tolerance absolute bound must be a number, got: {value}"))?;
+    let relative = match parts.next() {
+        Some(part) => part
+            .parse::<f64>()
+            .map_err(|_| format!("--float-tolerance relative bound must be a number, got: {value}"))?,
+        None => 0.0,
+    };
+    Ok((column.to_string(), FloatTolerance { absolute, relative }))
+}
+
+fn parse_dialect_byte(flag: &str, value: &str) -> Result<u8, String> {
+    match value {
+        "\\t" | "tab" => Ok(b'\t'),
+        _ => {
+            let mut bytes = value.bytes();
+            let byte = bytes
+                .next()
+                .ok_or_else(|| format!("{flag} requires a single-character value"))?;
+            if bytes.next().is_some() {
+                return Err(format!("{flag} must be a single byte, got: {value}"));
+            }
+            Ok(byte)
+        }
+    }
+}
+
+/// Mirrors `CliArgs`, but every setting is optional so a config file only
+/// needs to name the settings it wants to pin. Loaded as a lower-priority
+/// layer underneath whatever the command line passes explicitly.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+struct ConfigFile {
+    a: Option<String>,
+    b: Option<String>,
+    key: Option<Vec<String>>,
+    compare_by_keys: Option<String>,
+    header_mode: Option<String>,
+    ignore_column_order: Option<bool>,
+    emit_unchanged: Option<bool>,
+    emit_progress: Option<bool>,
+    partitions: Option<usize>,
+    no_partitions: Option<bool>,
+    merge_strategy: Option<String>,
+    partition_memory_budget: Option<usize>,
+    join_mode: Option<String>,
+    column_type: Option<Vec<String>>,
+    float_tolerance: Option<Vec<String>>,
+    timestamp_granularity: Option<Vec<String>>,
+    base: Option<String>,
+    field_diff: Option<String>,
+    field_diff_max_len: Option<usize>,
+    a_format: Option<String>,
+    b_format: Option<String>,
+    format: Option<String>,
+    out: Option<String>,
+    pretty: Option<bool>,
+    delimiter: Option<String>,
+    quote: Option<String>,
+    trim: Option<String>,
+    flexible: Option<bool>,
+    lossless_bytes: Option<bool>,
+    jobs: Option<usize>,
+    drop_equal_fields: Option<bool>,
+    ignore_row_order: Option<bool>,
+    streaming: Option<bool>,
+    output_mode: Option<String>,
+    join_selection: Option<String>,
+    exit_code: Option<bool>,
+    fail_on: Option<String>,
+}
+
+impl ConfigFile {
+    fn load(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("failed to read --config file {path}: {err}"))?;
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match extension.as_str() {
+            "toml" => toml::from_str(&contents)
+                .map_err(|err| format!("failed to parse --config file {path} as TOML: {err}")),
+            "yaml" | "yml" => serde_yaml::from_str(&contents)
+                .map_err(|err| format!("failed to parse --config file {path} as YAML: {err}")),
+            "json" => serde_json::from_str(&contents)
+                .map_err(|err| format!("failed to parse --config file {path} as JSON: {err}")),
+            other => Err(format!(
+                "unsupported --config file extension '{other}' (expected .toml, .yaml/.yml, or .json): {path}"
+            )),
+        }
+    }
+}
+
 fn parse_args() -> Result<CliArgs, String> {
     let mut a_path: Option<String> = None;
+    let mut base_path: Option<String> = None;
+    let mut field_diff: Option<FieldDiffMode> = None;
+    let mut field_diff_max_len: Option<usize> = None;
+    let mut a_format: Option<InputFormat> = None;
+    let mut b_format: Option<InputFormat> = None;
     let mut b_path: Option<String> = None;
     let mut key_columns: Vec<String> = Vec::new();
-    let mut header_mode = HeaderMode::Strict;
-    let mut emit_unchanged = false;
-    let mut emit_progress = false;
+    let mut header_mode: Option<HeaderMode> = None;
+    let mut emit_unchanged: Option<bool> = None;
+    let mut emit_progress: Option<bool> = None;
     let mut partition_count: Option<usize> = None;
-    let mut disable_partitions = false;
-    let mut output_format = OutputFormat::Jsonl;
-    let mut output_path: Option<String> = None;
-    let mut pretty = false;
-    let mut ignore_column_order = false;
+    let mut disable_partitions: Option<bool> = None;
+    let mut merge_strategy: Option<MergeStrategyArg> = None;
+    let mut partition_memory_budget: Option<usize> = None;
+    let mut join_mode: Option<JoinModeArg> = None;
+    let mut column_type_values: Vec<String> = Vec::new();
+    let mut float_tolerance_values: Vec<String> = Vec::new();
+    let mut timestamp_granularity_values: Vec<String> = Vec::new();
+    let mut output_format: Option<OutputFormat> = None;
+    let mut out_values: Vec<String> = Vec::new();
+    let mut pretty: Option<bool> = None;
+    let mut ignore_column_order: Option<bool> = None;
+    let mut delimiter: Option<u8> = None;
+    let mut quote: Option<u8> = None;
+    let mut trim: Option<CsvTrim> = None;
+    let mut flexible: Option<bool> = None;
+    let mut lossless_bytes: Option<bool> = None;
+    let mut jobs: Option<usize> = None;
+    let mut drop_equal_fields: Option<bool> = None;
+    let mut ignore_row_order: Option<bool> = None;
+    let mut streaming: Option<bool> = None;
+    let mut output_mode: Option<OutputMode> = None;
+    let mut join_selection: Option<JoinSelection> = None;
+    let mut config_path: Option<String> = None;
+    let mut exit_code: Option<bool> = None;
+    let mut fail_on: Option<FailOn> = None;
 
     let args: Vec<String> = env::args().skip(1).collect();
     let mut i = 0usize;
@@ -76,6 +435,31 @@ fn parse_args() -> Result<CliArgs, String> {
                     .ok_or_else(|| "--a requires a value".to_string())?;
                 a_path = Some(value.clone());
             }
+            "--base" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--base requires a value".to_string())?;
+                base_path = Some(value.clone());
+            }
+            "--field-diff" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--field-diff requires a value".to_string())?;
+                field_diff = Some(FieldDiffMode::parse(value).map_err(|e| e.message)?);
+            }
+            "--field-diff-max-len" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--field-diff-max-len requires a value".to_string())?;
+                field_diff_max_len = Some(
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| "--field-diff-max-len must be a positive integer".to_string())?,
+                );
+            }
             "--b" => {
                 i += 1;
                 let value = args
@@ -83,6 +467,20 @@ fn parse_args() -> Result<CliArgs, String> {
                     .ok_or_else(|| "--b requires a value".to_string())?;
                 b_path = Some(value.clone());
             }
+            "--a-format" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--a-format requires a value".to_string())?;
+                a_format = Some(InputFormat::parse(value)?);
+            }
+            "--b-format" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--b-format requires a value".to_string())?;
+                b_format = Some(InputFormat::parse(value)?);
+            }
             "--key" => {
                 i += 1;
                 let value = args
@@ -102,13 +500,13 @@ fn parse_args() -> Result<CliArgs, String> {
                 let value = args
                     .get(i)
                     .ok_or_else(|| "--header-mode requires a value".to_string())?;
-                header_mode = HeaderMode::parse(value).map_err(|e| e.message)?;
+                header_mode = Some(HeaderMode::parse(value).map_err(|e| e.message)?);
             }
             "--emit-unchanged" => {
-                emit_unchanged = true;
+                emit_unchanged = Some(true);
             }
             "--emit-progress" => {
-                emit_progress = true;
+                emit_progress = Some(true);
             }
             "--partitions" => {
                 i += 1;
@@ -124,27 +522,153 @@ fn parse_args() -> Result<CliArgs, String> {
                 partition_count = Some(parsed);
             }
             "--no-partitions" => {
-                disable_partitions = true;
+                disable_partitions = Some(true);
+            }
+            "--merge-strategy" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--merge-strategy requires a value".to_string())?;
+                merge_strategy = Some(MergeStrategyArg::parse(value)?);
+            }
+            "--partition-memory-budget" => {
+                i += 1;
+                let value = args.get(i).ok_or_else(|| {
+                    "--partition-memory-budget requires a value".to_string()
+                })?;
+                let parsed = value
+                    .parse::<usize>()
+                    .map_err(|_| "--partition-memory-budget must be a positive integer".to_string())?;
+                if parsed == 0 {
+                    return Err("--partition-memory-budget must be greater than zero".to_string());
+                }
+                partition_memory_budget = Some(parsed);
+            }
+            "--join-mode" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--join-mode requires a value".to_string())?;
+                join_mode = Some(JoinModeArg::parse(value)?);
+            }
+            "--column-type" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--column-type requires a value".to_string())?;
+                column_type_values.push(value.clone());
+            }
+            "--float-tolerance" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--float-tolerance requires a value".to_string())?;
+                float_tolerance_values.push(value.clone());
+            }
+            "--timestamp-granularity" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--timestamp-granularity requires a value".to_string())?;
+                timestamp_granularity_values.push(value.clone());
             }
             "--format" => {
                 i += 1;
                 let value = args
                     .get(i)
                     .ok_or_else(|| "--format requires a value".to_string())?;
-                output_format = OutputFormat::parse(value)?;
+                output_format = Some(OutputFormat::parse(value)?);
             }
             "--out" => {
                 i += 1;
                 let value = args
                     .get(i)
                     .ok_or_else(|| "--out requires a value".to_string())?;
-                output_path = Some(value.clone());
+                out_values.push(value.clone());
             }
             "--pretty" => {
-                pretty = true;
+                pretty = Some(true);
             }
             "--ignore-column-order" => {
-                ignore_column_order = true;
+                ignore_column_order = Some(true);
+            }
+            "--delimiter" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--delimiter requires a value".to_string())?;
+                delimiter = Some(parse_dialect_byte("--delimiter", value)?);
+            }
+            "--quote" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--quote requires a value".to_string())?;
+                quote = Some(parse_dialect_byte("--quote", value)?);
+            }
+            "--trim" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--trim requires a value".to_string())?;
+                trim = Some(CsvTrim::parse(value).map_err(|e| e.message)?);
+            }
+            "--flexible" => {
+                flexible = Some(true);
+            }
+            "--lossless-bytes" => {
+                lossless_bytes = Some(true);
+            }
+            "--jobs" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--jobs requires a value".to_string())?;
+                jobs = Some(
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| "--jobs must be a non-negative integer".to_string())?,
+                );
+            }
+            "--drop-equal-fields" => {
+                drop_equal_fields = Some(true);
+            }
+            "--ignore-row-order" => {
+                ignore_row_order = Some(true);
+            }
+            "--streaming" => {
+                streaming = Some(true);
+            }
+            "--output-mode" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--output-mode requires a value".to_string())?;
+                output_mode = Some(OutputMode::parse(value).map_err(|e| e.message)?);
+            }
+            "--join-selection" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--join-selection requires a value".to_string())?;
+                join_selection = Some(JoinSelection::parse(value).map_err(|e| e.message)?);
+            }
+            "--config" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--config requires a value".to_string())?;
+                config_path = Some(value.clone());
+            }
+            "--exit-code" => {
+                exit_code = Some(true);
+            }
+            "--fail-on" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--fail-on requires a value".to_string())?;
+                fail_on = Some(FailOn::parse(value)?);
             }
             "-h" | "--help" => {
                 return Err(help_text());
@@ -156,19 +680,250 @@ fn parse_args() -> Result<CliArgs, String> {
         i += 1;
     }
 
+    let config = match &config_path {
+        Some(path) => ConfigFile::load(path)?,
+        None => ConfigFile::default(),
+    };
+
+    let tee_targets: Vec<(OutputFormat, Option<String>)> = out_values
+        .iter()
+        .filter_map(|value| parse_tee_target(value))
+        .collect();
+    if !tee_targets.is_empty() && tee_targets.len() != out_values.len() {
+        return Err(format!(
+            "Cannot mix --out <path> with repeated --out <format>=<path> targets; use one style consistently\n\n{}",
+            help_text()
+        ));
+    }
+    let output_path = if tee_targets.is_empty() {
+        out_values.last().cloned()
+    } else {
+        None
+    };
+
+    let key_columns = if !key_columns.is_empty() {
+        key_columns
+    } else {
+        let mut merged = config.key.unwrap_or_default();
+        if let Some(compare_by_keys) = config.compare_by_keys {
+            merged.extend(parse_key_csv(&compare_by_keys));
+        }
+        merged
+    };
+    let header_mode = match header_mode {
+        Some(mode) => mode,
+        None => match config.header_mode {
+            Some(value) => HeaderMode::parse(&value).map_err(|e| e.message)?,
+            None => HeaderMode::Strict,
+        },
+    };
+    let output_format = match output_format {
+        Some(format) => format,
+        None => match config.format {
+            Some(value) => OutputFormat::parse(&value)?,
+            None => OutputFormat::Jsonl,
+        },
+    };
+    let trim = match trim {
+        Some(trim) => trim,
+        None => match config.trim {
+            Some(value) => CsvTrim::parse(&value).map_err(|e| e.message)?,
+            None => CsvTrim::None,
+        },
+    };
+    let delimiter = match delimiter {
+        Some(byte) => byte,
+        None => match config.delimiter {
+            Some(value) => parse_dialect_byte("config delimiter", &value)?,
+            None => b',',
+        },
+    };
+    let quote = match quote {
+        Some(byte) => byte,
+        None => match config.quote {
+            Some(value) => parse_dialect_byte("config quote", &value)?,
+            None => b'"',
+        },
+    };
+    let fail_on = match fail_on {
+        Some(fail_on) => fail_on,
+        None => match config.fail_on {
+            Some(value) => FailOn::parse(&value)?,
+            None => FailOn::Any,
+        },
+    };
+    let merge_strategy = match merge_strategy {
+        Some(strategy) => strategy,
+        None => match config.merge_strategy {
+            Some(value) => MergeStrategyArg::parse(&value)?,
+            None => MergeStrategyArg::Hashed,
+        },
+    };
+    let partition_memory_budget = partition_memory_budget.or(config.partition_memory_budget);
+    let join_mode = match join_mode {
+        Some(mode) => Some(mode),
+        None => match config.join_mode {
+            Some(value) => Some(JoinModeArg::parse(&value)?),
+            None => None,
+        },
+    };
+    if join_mode.is_some() && key_columns.is_empty() {
+        return Err("--join-mode requires --key or --compare-by-keys".to_string());
+    }
+    let streaming = streaming.or(config.streaming).unwrap_or(false);
+    if streaming && join_mode.is_some() {
+        return Err("--streaming cannot be combined with --join-mode".to_string());
+    }
+    if join_mode.is_some() && lossless_bytes.or(config.lossless_bytes).unwrap_or(false) {
+        return Err("--lossless-bytes cannot be combined with --join-mode".to_string());
+    }
+    let base_path = base_path.or(config.base);
+    if base_path.is_some() {
+        if join_mode.is_some() {
+            return Err("--base cannot be combined with --join-mode".to_string());
+        }
+        if key_columns.is_empty() {
+            return Err("--base requires --key or --compare-by-keys".to_string());
+        }
+        if streaming {
+            return Err("--streaming cannot be combined with --base".to_string());
+        }
+    }
+    let output_mode = match output_mode {
+        Some(mode) => mode,
+        None => match config.output_mode {
+            Some(value) => OutputMode::parse(&value).map_err(|e| e.message)?,
+            None => OutputMode::default(),
+        },
+    };
+    let join_selection = match join_selection {
+        Some(selection) => selection,
+        None => match config.join_selection {
+            Some(value) => JoinSelection::parse(&value).map_err(|e| e.message)?,
+            None => JoinSelection::default(),
+        },
+    };
+    let field_diff = match field_diff {
+        Some(mode) => mode,
+        None => match config.field_diff {
+            Some(value) => FieldDiffMode::parse(&value).map_err(|e| e.message)?,
+            None => FieldDiffMode::default(),
+        },
+    };
+    let field_diff_max_len = field_diff_max_len
+        .or(config.field_diff_max_len)
+        .unwrap_or(4096);
+    let a_format = match a_format {
+        Some(format) => format,
+        None => match &config.a_format {
+            Some(value) => InputFormat::parse(value)?,
+            None => InputFormat::Csv,
+        },
+    };
+    let b_format = match b_format {
+        Some(format) => format,
+        None => match &config.b_format {
+            Some(value) => InputFormat::parse(value)?,
+            None => InputFormat::Csv,
+        },
+    };
+    if (a_format != InputFormat::Csv || b_format != InputFormat::Csv) && key_columns.is_empty() {
+        return Err("--a-format/--b-format other than csv require --key or --compare-by-keys".to_string());
+    }
+    if (a_format != InputFormat::Csv || b_format != InputFormat::Csv) && join_mode.is_some() {
+        return Err("--a-format/--b-format other than csv cannot be combined with --join-mode".to_string());
+    }
+    if (a_format != InputFormat::Csv || b_format != InputFormat::Csv) && streaming {
+        return Err("--a-format/--b-format other than csv cannot be combined with --streaming".to_string());
+    }
+    if (a_format != InputFormat::Csv || b_format != InputFormat::Csv) && base_path.is_some() {
+        return Err("--a-format/--b-format other than csv cannot be combined with --base".to_string());
+    }
+
+    let column_type_values = if column_type_values.is_empty() {
+        config.column_type.clone().unwrap_or_default()
+    } else {
+        column_type_values
+    };
+    let mut column_types = HashMap::new();
+    for value in &column_type_values {
+        let (column, ty) = parse_column_pair("--column-type", value)?;
+        column_types.insert(column.to_string(), ColumnType::parse(ty).map_err(|e| e.message)?);
+    }
+
+    let float_tolerance_values = if float_tolerance_values.is_empty() {
+        config.float_tolerance.clone().unwrap_or_default()
+    } else {
+        float_tolerance_values
+    };
+    let mut float_tolerance = HashMap::new();
+    for value in &float_tolerance_values {
+        let (column, tolerance) = parse_float_tolerance(value)?;
+        float_tolerance.insert(column, tolerance);
+    }
+
+    let timestamp_granularity_values = if timestamp_granularity_values.is_empty() {
+        config.timestamp_granularity.clone().unwrap_or_default()
+    } else {
+        timestamp_granularity_values
+    };
+    let mut timestamp_granularity = HashMap::new();
+    for value in &timestamp_granularity_values {
+        let (column, granularity) = parse_column_pair("--timestamp-granularity", value)?;
+        timestamp_granularity.insert(
+            column.to_string(),
+            TimestampGranularity::parse(granularity).map_err(|e| e.message)?,
+        );
+    }
+
     Ok(CliArgs {
-        a_path: a_path.ok_or_else(|| format!("--a is required\n\n{}", help_text()))?,
-        b_path: b_path.ok_or_else(|| format!("--b is required\n\n{}", help_text()))?,
+        a_path: a_path
+            .or(config.a)
+            .ok_or_else(|| format!("--a is required\n\n{}", help_text()))?,
+        b_path: b_path
+            .or(config.b)
+            .ok_or_else(|| format!("--b is required\n\n{}", help_text()))?,
         key_columns,
         header_mode,
-        emit_unchanged,
-        emit_progress,
-        partition_count,
-        disable_partitions,
+        emit_unchanged: emit_unchanged.or(config.emit_unchanged).unwrap_or(false),
+        emit_progress: emit_progress.or(config.emit_progress).unwrap_or(false),
+        partition_count: partition_count.or(config.partitions),
+        disable_partitions: disable_partitions.or(config.no_partitions).unwrap_or(false),
+        merge_strategy,
+        partition_memory_budget,
+        join_mode,
+        column_types,
+        float_tolerance,
+        timestamp_granularity,
+        base_path,
+        field_diff,
+        field_diff_max_len,
+        a_format,
+        b_format,
         output_format,
-        output_path,
-        pretty,
-        ignore_column_order,
+        output_path: output_path.or(config.out),
+        pretty: pretty.or(config.pretty).unwrap_or(false),
+        ignore_column_order: ignore_column_order
+            .or(config.ignore_column_order)
+            .unwrap_or(false),
+        delimiter,
+        quote,
+        trim,
+        flexible: flexible.or(config.flexible).unwrap_or(false),
+        lossless_bytes: lossless_bytes.or(config.lossless_bytes).unwrap_or(false),
+        jobs: jobs.or(config.jobs).unwrap_or(0),
+        drop_equal_fields: drop_equal_fields
+            .or(config.drop_equal_fields)
+            .unwrap_or(false),
+        ignore_row_order: ignore_row_order
+            .or(config.ignore_row_order)
+            .unwrap_or(false),
+        streaming,
+        output_mode,
+        join_selection,
+        exit_code: exit_code.or(config.exit_code).unwrap_or(false),
+        fail_on,
+        tee_targets,
     })
 }
 
@@ -179,6 +934,8 @@ fn help_text() -> String {
         "",
         "Options:",
         "  --a <path>                 Path to CSV A",
+        "  --base <path>              Path to the common ancestor CSV; enables a three-way merge",
+        "                             of A and B instead of a diff (requires --key)",
         "  --b <path>                 Path to CSV B",
         "  (default compare mode is positional when no keys are provided)",
         "  --key <column>             Key column (repeat for keyed mode)",
@@ -189,9 +946,44 @@ fn help_text() -> String {
         "  --emit-progress            Emit progress events",
         "  --partitions <n>           Override partition count for partitioned engine path",
         "  --no-partitions            Force non-partitioned core path",
-        "  --format <mode>            jsonl (default) | json | summary",
+        "  --merge-strategy <mode>    hashed (default) | sorted; only applies with --partitions",
+        "  --partition-memory-budget <n>  Re-partition a partition once a side exceeds n rows",
+        "  --join-mode <mode>         inner | left | right | full | left-anti | right-anti",
+        "                             Emit a side-by-side join instead of a diff (requires --key)",
+        "  --column-type <col>=<type>  Repeatable: int | float | decimal | bool | timestamp | string",
+        "  --float-tolerance <col>=<absolute>[,<relative>]  Repeatable",
+        "  --timestamp-granularity <col>=<granularity>  Repeatable: nanosecond | microsecond |",
+        "                             millisecond | second | minute | hour | day",
+        "  --field-diff <mode>        none (default) | line | word | char; cell-level diff granularity",
+        "  --field-diff-max-len <n>   Cells longer than this many bytes skip cell-level diffing",
+        "  --a-format <fmt>           csv (default) | ndjson | parquet; format of --a",
+        "  --b-format <fmt>           csv (default) | ndjson | parquet; format of --b",
+        "                             (either other than csv requires --key; incompatible with",
+        "                             --join-mode/--base)",
+        "  --format <mode>            jsonl (default) | json | summary | csv",
         "  --out <path>               Write output to a file instead of stdout",
+        "  --out <format>=<path>      Repeatable: tee another format to path ('-' for stdout)",
         "  --pretty                   Pretty-print JSON",
+        "  --delimiter <char>         Field delimiter (default ','); 'tab' or '\\t' for TSV",
+        "  --quote <char>             Quote character (default '\"')",
+        "  --trim <mode>              none (default) | fields | all",
+        "  --flexible                 Tolerate rows with fewer/more fields than the header",
+        "  --lossless-bytes           Base64-encode non-UTF-8 cells instead of lossily replacing them",
+        "                             (incompatible with --join-mode)",
+        "  --jobs <n>                 Worker threads for the in-memory diff path (default: auto-detect)",
+        "  --drop-equal-fields        Omit unchanged fields from changed-row events, keeping only",
+        "                             key columns and fields that actually differ",
+        "  --ignore-row-order         Compare rows as a multiset instead of by position/key order",
+        "  --streaming                Stream events to the sink as they're produced instead of",
+        "                             collecting them into a list first; skips --emit-progress",
+        "                             (only applies to the unpartitioned CSV diff path)",
+        "  --output-mode <mode>       diff (default) | join; join emits a reconciliation-style",
+        "                             row per selected key instead of added/removed/changed events",
+        "  --join-selection <sel>     left | right | inner | full (default); which keys",
+        "                             --output-mode join includes",
+        "  --config <path>            Load defaults from a .toml/.yaml/.json file; CLI flags win",
+        "  --exit-code                Exit with a nonzero status when matching differences are found",
+        "  --fail-on <mode>           any (default) | changed | added | removed",
     ]
     .join("\n")
 }
@@ -207,14 +999,148 @@ fn encode_json(value: &serde_json::Value, pretty: bool) -> String {
 struct JsonlSink {
     writer: Box<dyn Write>,
     pretty: bool,
+    counts: DiffCounts,
 }
 
 impl EventSink for JsonlSink {
     fn on_event(&mut self, event: &serde_json::Value) -> Result<(), String> {
+        let event_type = event
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        self.counts.observe(event_type);
         writeln!(self.writer, "{}", encode_json(event, self.pretty)).map_err(|err| err.to_string())
     }
 }
 
+/// Renders change events as a tabular patch: one row per changed field,
+/// with `change_type`/key columns/`column`/`old`/`new` — reopenable in a
+/// spreadsheet or another CSV pipeline, unlike the JSON-shaped formats.
+struct CsvSink {
+    writer: csv::Writer<Box<dyn Write>>,
+    key_columns: Vec<String>,
+    counts: DiffCounts,
+}
+
+impl CsvSink {
+    fn new(writer: Box<dyn Write>, key_columns: Vec<String>) -> Result<Self, String> {
+        let mut writer = csv::Writer::from_writer(writer);
+        let mut header = vec!["change_type".to_string()];
+        header.extend(key_columns.iter().cloned());
+        header.push("column".to_string());
+        header.push("old".to_string());
+        header.push("new".to_string());
+        writer
+            .write_record(&header)
+            .map_err(|err| format!("failed to write CSV header: {err}"))?;
+        Ok(Self {
+            writer,
+            key_columns,
+            counts: DiffCounts::default(),
+        })
+    }
+
+    fn key_values(&self, event: &Value) -> Vec<String> {
+        let key = event.get("key");
+        self.key_columns
+            .iter()
+            .map(|column| {
+                key.and_then(|k| k.get(column))
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn write_row(
+        &mut self,
+        change_type: &str,
+        key_values: &[String],
+        column: &str,
+        old: &str,
+        new: &str,
+    ) -> Result<(), String> {
+        let mut record = vec![change_type.to_string()];
+        record.extend(key_values.iter().cloned());
+        record.push(column.to_string());
+        record.push(old.to_string());
+        record.push(new.to_string());
+        self.writer
+            .write_record(&record)
+            .map_err(|err| format!("failed to write CSV row: {err}"))
+    }
+
+    fn finish(mut self) -> Result<(), String> {
+        self.writer
+            .flush()
+            .map_err(|err| format!("failed to flush CSV output: {err}"))
+    }
+}
+
+impl Drop for CsvSink {
+    // Ensures a CsvSink used behind a `Box<dyn EventSink>` (as in tee
+    // mode, where `finish` can't be called) still flushes its writer.
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+impl EventSink for CsvSink {
+    fn on_event(&mut self, event: &Value) -> Result<(), String> {
+        let event_type = event
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        self.counts.observe(event_type);
+        match event_type {
+            "added" | "removed" => {
+                let key_values = self.key_values(event);
+                let Some(row) = event.get("row").and_then(Value::as_object) else {
+                    return Ok(());
+                };
+                for (column, value) in row {
+                    let value_str = value.as_str().unwrap_or_default();
+                    let (old, new) = if event_type == "added" {
+                        ("", value_str)
+                    } else {
+                        (value_str, "")
+                    };
+                    self.write_row(event_type, &key_values, column, old, new)?;
+                }
+                Ok(())
+            }
+            "changed" => {
+                let key_values = self.key_values(event);
+                let changed = event
+                    .get("changed")
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
+                let delta = event.get("delta").and_then(Value::as_object);
+                for column in &changed {
+                    let column = column.as_str().unwrap_or_default();
+                    let (from, to) = delta
+                        .and_then(|d| d.get(column))
+                        .map(|entry| {
+                            (
+                                entry
+                                    .get("from")
+                                    .and_then(Value::as_str)
+                                    .unwrap_or_default(),
+                                entry.get("to").and_then(Value::as_str).unwrap_or_default(),
+                            )
+                        })
+                        .unwrap_or_default();
+                    self.write_row("changed", &key_values, column, from, to)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 struct CollectSink {
     events: Vec<Value>,
 }
@@ -226,6 +1152,19 @@ impl EventSink for CollectSink {
     }
 }
 
+/// Like `CollectSink`, but shares its buffer so it can be used alongside
+/// other sinks inside a `TeeSink` and still be read back afterward.
+struct SharedCollectSink {
+    events: Rc<RefCell<Vec<Value>>>,
+}
+
+impl EventSink for SharedCollectSink {
+    fn on_event(&mut self, event: &Value) -> Result<(), String> {
+        self.events.borrow_mut().push(event.clone());
+        Ok(())
+    }
+}
+
 fn open_output_writer(path: Option<&str>) -> Result<Box<dyn Write>, String> {
     match path {
         Some(path) => File::create(path)
@@ -260,6 +1199,74 @@ fn columns_from_schema<'a>(events: &'a [Value], key: &str) -> Option<Vec<&'a str
     Some(columns.iter().filter_map(Value::as_str).collect())
 }
 
+/// Counts, for each column, how many `changed` events touched it.
+fn column_change_counts(events: &[Value]) -> HashMap<String, u64> {
+    let mut counts: HashMap<String, u64> = HashMap::new();
+    for event in events {
+        if event.get("type").and_then(Value::as_str) != Some("changed") {
+            continue;
+        }
+        let Some(changed) = event.get("changed").and_then(Value::as_array) else {
+            continue;
+        };
+        for column in changed {
+            if let Some(column) = column.as_str() {
+                *counts.entry(column.to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// Describes how the column set differs between A and B: columns only
+/// in one side, and whether the columns common to both changed order.
+fn schema_diff_lines(columns_a: &[&str], columns_b: &[&str]) -> Vec<String> {
+    let set_a: HashSet<&str> = columns_a.iter().copied().collect();
+    let set_b: HashSet<&str> = columns_b.iter().copied().collect();
+
+    let added: Vec<&str> = columns_b
+        .iter()
+        .copied()
+        .filter(|column| !set_a.contains(column))
+        .collect();
+    let removed: Vec<&str> = columns_a
+        .iter()
+        .copied()
+        .filter(|column| !set_b.contains(column))
+        .collect();
+    let common_a: Vec<&str> = columns_a
+        .iter()
+        .copied()
+        .filter(|column| set_b.contains(column))
+        .collect();
+    let common_b: Vec<&str> = columns_b
+        .iter()
+        .copied()
+        .filter(|column| set_a.contains(column))
+        .collect();
+    let reordered = common_a != common_b;
+
+    vec![
+        format!(
+            "columns_added:     {}",
+            if added.is_empty() {
+                "<none>".to_string()
+            } else {
+                added.join(",")
+            }
+        ),
+        format!(
+            "columns_removed:   {}",
+            if removed.is_empty() {
+                "<none>".to_string()
+            } else {
+                removed.join(",")
+            }
+        ),
+        format!("columns_reordered: {reordered}"),
+    ]
+}
+
 fn build_summary_report(events: &[Value]) -> String {
     let stats = stats_from_events(events);
     let columns_a = columns_from_schema(events, "columns_a").unwrap_or_default();
@@ -311,10 +1318,101 @@ fn build_summary_report(events: &[Value]) -> String {
         format!("rows_removed:        {rows_removed}"),
         format!("rows_changed:        {rows_changed}"),
         format!("rows_unchanged:      {rows_unchanged}"),
+        "".to_string(),
+        "schema diff".to_string(),
+        "-----------".to_string(),
     ]
+    .into_iter()
+    .chain(schema_diff_lines(&columns_a, &columns_b))
+    .chain([
+        "".to_string(),
+        "column changes".to_string(),
+        "--------------".to_string(),
+    ])
+    .chain({
+        let counts = column_change_counts(events);
+        let mut columns: Vec<&String> = counts.keys().collect();
+        columns.sort();
+        if columns.is_empty() {
+            vec!["<none>".to_string()]
+        } else {
+            columns
+                .into_iter()
+                .map(|column| format!("{column}: {}", counts[column]))
+                .collect()
+        }
+    })
+    .collect::<Vec<String>>()
     .join("\n")
 }
 
+/// Runs the comparison `args` selected (a keyed/positional diff, or a
+/// side-by-side join when `--join-mode` is set) and streams its events
+/// through `sink`. Joins always go over the partitioned engine path, since
+/// `join_partitioned_from_manifest` only operates on a `PartitionManifest`.
+fn run_selected(
+    args: &CliArgs,
+    options: &DiffOptions,
+    run_config: &EngineRunConfig,
+    sink: &mut dyn EventSink,
+) -> Result<(), EngineError> {
+    if let Some(join_mode) = args.join_mode {
+        let partitions = run_config
+            .partition_count
+            .unwrap_or(DEFAULT_JOIN_PARTITIONS);
+        let manifest = partition_inputs_to_spill_for_join(
+            Path::new(&args.a_path),
+            Path::new(&args.b_path),
+            options,
+            partitions,
+            run_config.spill_policy,
+        )?;
+        return run_join_to_sink(
+            &manifest,
+            options,
+            join_mode.into_engine(),
+            &NeverCancel,
+            sink,
+        );
+    }
+
+    if let Some(base_path) = &args.base_path {
+        let partitions = run_config
+            .partition_count
+            .unwrap_or(DEFAULT_JOIN_PARTITIONS);
+        let manifest = partition_inputs3_to_spill(
+            Path::new(base_path),
+            Path::new(&args.a_path),
+            Path::new(&args.b_path),
+            options,
+            partitions,
+            run_config.spill_policy,
+        )?;
+        return run_merge_to_sink(&manifest, options, &AutoMergeSolver, &NeverCancel, sink);
+    }
+
+    if args.a_format != InputFormat::Csv || args.b_format != InputFormat::Csv {
+        let partitions = run_config
+            .partition_count
+            .unwrap_or(DEFAULT_JOIN_PARTITIONS);
+        let source_a = args.a_format.open(Path::new(&args.a_path), "a", options)?;
+        let source_b = args.b_format.open(Path::new(&args.b_path), "b", options)?;
+        let spill: Box<dyn SpillStore> =
+            Box::new(TempDirSpill::new(partitions, run_config.spill_policy)?);
+        let manifest = partition_sources_to_spill_with_store(source_a, source_b, options, spill)?;
+        return run_partitioned_manifest_to_sink(&manifest, options, run_config, &NeverCancel, sink);
+    }
+
+    run_keyed_to_sink_with_config(
+        Path::new(&args.a_path),
+        Path::new(&args.b_path),
+        options,
+        run_config,
+        &NeverCancel,
+        sink,
+    )
+}
+
 fn render_error_and_exit(error: EngineError) -> ! {
     match error {
         EngineError::Diff(err) => {
@@ -366,21 +1464,107 @@ fn main() {
     };
 
     let options = DiffOptions {
-        key_columns: args.key_columns,
+        key_columns: args.key_columns.clone(),
         header_mode: if args.ignore_column_order {
             HeaderMode::Sorted
         } else {
             args.header_mode
         },
         emit_unchanged: args.emit_unchanged,
+        delimiter: args.delimiter,
+        quote: args.quote,
+        trim: args.trim,
+        flexible: args.flexible,
+        lossless_bytes: args.lossless_bytes,
+        jobs: args.jobs,
+        drop_equal_fields: args.drop_equal_fields,
+        ignore_row_order: args.ignore_row_order,
+        output_mode: args.output_mode,
+        join_selection: args.join_selection,
+        column_types: args.column_types.clone(),
+        float_tolerance: args.float_tolerance.clone(),
+        timestamp_granularity: args.timestamp_granularity.clone(),
+        field_diff: args.field_diff,
+        field_diff_max_len: args.field_diff_max_len,
+        ..DiffOptions::default()
+    };
+    let mut run_config = EngineRunConfig {
+        emit_progress: args.emit_progress,
+        merge_strategy: args.merge_strategy.into_engine(),
+        streaming: args.streaming,
+        ..EngineRunConfig::default()
     };
-    let mut run_config = EngineRunConfig::default();
-    run_config.emit_progress = args.emit_progress;
     if args.disable_partitions {
         run_config.partition_count = None;
     } else if let Some(partition_count) = args.partition_count {
         run_config.partition_count = Some(partition_count);
     }
+    if let Some(budget) = args.partition_memory_budget {
+        run_config.partition_memory_budget = budget;
+    }
+
+    if !args.tee_targets.is_empty() {
+        let buffered_events: Rc<RefCell<Vec<Value>>> = Rc::new(RefCell::new(Vec::new()));
+        let mut sinks: Vec<Box<dyn EventSink>> = vec![Box::new(SharedCollectSink {
+            events: Rc::clone(&buffered_events),
+        })];
+
+        for (format, path) in &args.tee_targets {
+            match format {
+                OutputFormat::Jsonl => {
+                    let writer = open_output_writer(path.as_deref()).unwrap_or_else(|message| {
+                        eprintln!("{message}");
+                        std::process::exit(2);
+                    });
+                    sinks.push(Box::new(JsonlSink {
+                        writer,
+                        pretty: args.pretty,
+                        counts: DiffCounts::default(),
+                    }));
+                }
+                OutputFormat::Csv => {
+                    let writer = open_output_writer(path.as_deref()).unwrap_or_else(|message| {
+                        eprintln!("{message}");
+                        std::process::exit(2);
+                    });
+                    let sink = CsvSink::new(writer, options.key_columns.clone()).unwrap_or_else(
+                        |message| {
+                            eprintln!("{message}");
+                            std::process::exit(2);
+                        },
+                    );
+                    sinks.push(Box::new(sink));
+                }
+                // Rendered from `buffered_events` once the run completes.
+                OutputFormat::Json | OutputFormat::Summary => {}
+            }
+        }
+
+        let mut tee = TeeSink::new(sinks);
+        if let Err(err) = run_selected(&args, &options, &run_config, &mut tee) {
+            render_error_and_exit(err);
+        }
+
+        let events = buffered_events.borrow();
+        for (format, path) in &args.tee_targets {
+            let rendered = match format {
+                OutputFormat::Json => Some(encode_json(&Value::Array(events.clone()), args.pretty)),
+                OutputFormat::Summary => Some(build_summary_report(&events)),
+                OutputFormat::Jsonl | OutputFormat::Csv => None,
+            };
+            if let Some(rendered) = rendered {
+                if let Err(message) = write_output(path.as_deref(), &rendered) {
+                    eprintln!("{message}");
+                    std::process::exit(2);
+                }
+            }
+        }
+
+        if args.exit_code && DiffCounts::from_stats_events(&events).has_failures(args.fail_on) {
+            std::process::exit(DIFFERENCES_FOUND_EXIT_CODE);
+        }
+        return;
+    }
 
     match args.output_format {
         OutputFormat::Jsonl => {
@@ -392,40 +1576,58 @@ fn main() {
             let mut sink = JsonlSink {
                 writer,
                 pretty: args.pretty,
+                counts: DiffCounts::default(),
             };
-            if let Err(err) = run_keyed_to_sink_with_config(
-                Path::new(&args.a_path),
-                Path::new(&args.b_path),
-                &options,
-                &run_config,
-                &NeverCancel,
-                &mut sink,
-            ) {
+            if let Err(err) = run_selected(&args, &options, &run_config, &mut sink) {
+                render_error_and_exit(err);
+            }
+            if args.exit_code && sink.counts.has_failures(args.fail_on) {
+                std::process::exit(DIFFERENCES_FOUND_EXIT_CODE);
+            }
+        }
+        OutputFormat::Csv => {
+            let writer =
+                open_output_writer(args.output_path.as_deref()).unwrap_or_else(|message| {
+                    eprintln!("{message}");
+                    std::process::exit(2);
+                });
+            let mut sink =
+                CsvSink::new(writer, options.key_columns.clone()).unwrap_or_else(|message| {
+                    eprintln!("{message}");
+                    std::process::exit(2);
+                });
+            if let Err(err) = run_selected(&args, &options, &run_config, &mut sink) {
                 render_error_and_exit(err);
             }
+            let has_failures = sink.counts.has_failures(args.fail_on);
+            if let Err(message) = sink.finish() {
+                eprintln!("{message}");
+                std::process::exit(2);
+            }
+            if args.exit_code && has_failures {
+                std::process::exit(DIFFERENCES_FOUND_EXIT_CODE);
+            }
         }
         OutputFormat::Json | OutputFormat::Summary => {
             let mut sink = CollectSink { events: Vec::new() };
-            if let Err(err) = run_keyed_to_sink_with_config(
-                Path::new(&args.a_path),
-                Path::new(&args.b_path),
-                &options,
-                &run_config,
-                &NeverCancel,
-                &mut sink,
-            ) {
+            if let Err(err) = run_selected(&args, &options, &run_config, &mut sink) {
                 render_error_and_exit(err);
             }
 
             let rendered = match args.output_format {
-                OutputFormat::Json => encode_json(&Value::Array(sink.events), args.pretty),
+                OutputFormat::Json => encode_json(&Value::Array(sink.events.clone()), args.pretty),
                 OutputFormat::Summary => build_summary_report(&sink.events),
-                OutputFormat::Jsonl => unreachable!(),
+                OutputFormat::Jsonl | OutputFormat::Csv => unreachable!(),
             };
             if let Err(message) = write_output(args.output_path.as_deref(), &rendered) {
                 eprintln!("{message}");
                 std::process::exit(2);
             }
+            if args.exit_code
+                && DiffCounts::from_stats_events(&sink.events).has_failures(args.fail_on)
+            {
+                std::process::exit(DIFFERENCES_FOUND_EXIT_CODE);
+            }
         }
     }
 }
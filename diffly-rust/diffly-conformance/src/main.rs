@@ -5,7 +5,8 @@ use diffly_core::{diff_csv_files, DiffError, DiffOptions, HeaderMode};
 use diffly_engine::{
     run_keyed_to_sink_with_config, EngineError, EngineRunConfig, EventSink, NeverCancel,
 };
-use serde_json::Value;
+use regex::Regex;
+use serde_json::{json, Value};
 
 fn load_jsonl(path: &Path) -> Result<Vec<Value>, String> {
     let content = fs::read_to_string(path)
@@ -74,9 +75,245 @@ fn parse_options(config: &Value) -> Result<DiffOptions, DiffError> {
         key_columns,
         header_mode: HeaderMode::parse(header_mode)?,
         emit_unchanged,
+        ..DiffOptions::default()
     })
 }
 
+#[derive(Debug, Clone, Default)]
+struct NormalizeConfig {
+    redact_pointers: Vec<String>,
+    sort_by_key: bool,
+}
+
+fn parse_normalize_config(config: &Value) -> NormalizeConfig {
+    let Some(section) = config.get("normalize") else {
+        return NormalizeConfig::default();
+    };
+    let redact_pointers = section
+        .get("redact_pointers")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(ToString::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    let sort_by_key = section
+        .get("sort_by_key")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    NormalizeConfig {
+        redact_pointers,
+        sort_by_key,
+    }
+}
+
+const REDACTED_PLACEHOLDER: &str = "<redacted>";
+
+fn redact_pointers(events: &mut [Value], pointers: &[String]) {
+    for event in events.iter_mut() {
+        for pointer in pointers {
+            if let Some(slot) = event.pointer_mut(pointer) {
+                *slot = Value::String(REDACTED_PLACEHOLDER.to_string());
+            }
+        }
+    }
+}
+
+// Collapses whole-number floats (e.g. 1.0) to integers so fixtures don't churn
+// on incidental float vs. int formatting differences between engine paths.
+fn canonicalize_numbers(value: &mut Value) {
+    match value {
+        Value::Number(number) => {
+            if let Some(as_f64) = number.as_f64() {
+                if as_f64.fract() == 0.0 && as_f64.abs() < 1e15 {
+                    *value = json!(as_f64 as i64);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(canonicalize_numbers),
+        Value::Object(map) => map.values_mut().for_each(canonicalize_numbers),
+        _ => {}
+    }
+}
+
+fn event_sort_key(event: &Value) -> Option<String> {
+    event.get("key").map(Value::to_string)
+}
+
+// Sorts by the `key` field so partitioned runs (which interleave rows across
+// partitions) compare equal to a single-threaded run's sorted-key order.
+fn sort_events_by_key(events: &mut [Value]) {
+    events.sort_by_key(event_sort_key);
+}
+
+fn normalize_events(events: &[Value], normalize: &NormalizeConfig) -> Vec<Value> {
+    let mut events = events.to_vec();
+    redact_pointers(&mut events, &normalize.redact_pointers);
+    for event in &mut events {
+        canonicalize_numbers(event);
+    }
+    if normalize.sort_by_key {
+        sort_events_by_key(&mut events);
+    }
+    events
+}
+
+enum LineDiffOp {
+    Equal(usize),
+    OnlyInActual(usize),
+    OnlyInExpected(usize),
+}
+
+fn canonical_line(event: &Value) -> String {
+    serde_json::to_string(event).unwrap_or_else(|_| "<serialize failed>".to_string())
+}
+
+// Standard O(n*m) LCS alignment; fixtures are small enough that this is never
+// the bottleneck, and it keeps the implementation easy to follow.
+fn lcs_align(actual: &[String], expected: &[String]) -> Vec<LineDiffOp> {
+    let n = actual.len();
+    let m = expected.len();
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if actual[i] == expected[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0usize, 0usize);
+    while i < n && j < m {
+        if actual[i] == expected[j] {
+            ops.push(LineDiffOp::Equal(i));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            ops.push(LineDiffOp::OnlyInActual(i));
+            i += 1;
+        } else {
+            ops.push(LineDiffOp::OnlyInExpected(j));
+            j += 1;
+        }
+    }
+    while i < n {
+        ops.push(LineDiffOp::OnlyInActual(i));
+        i += 1;
+    }
+    while j < m {
+        ops.push(LineDiffOp::OnlyInExpected(j));
+        j += 1;
+    }
+    ops
+}
+
+const DIFF_CONTEXT_LINES: usize = 2;
+
+// Renders only the differing regions of an LCS alignment, git-diff style,
+// instead of dumping both full event arrays.
+fn render_events_diff(actual: &[Value], expected: &[Value]) -> String {
+    let actual_lines: Vec<String> = actual.iter().map(canonical_line).collect();
+    let expected_lines: Vec<String> = expected.iter().map(canonical_line).collect();
+    let ops = lcs_align(&actual_lines, &expected_lines);
+
+    let mut runs: Vec<(bool, Vec<&LineDiffOp>)> = Vec::new();
+    for op in &ops {
+        let is_equal = matches!(op, LineDiffOp::Equal(..));
+        match runs.last_mut() {
+            Some((last_equal, group)) if *last_equal == is_equal => group.push(op),
+            _ => runs.push((is_equal, vec![op])),
+        }
+    }
+
+    let mut added = 0usize;
+    let mut removed = 0usize;
+    let mut changed = 0usize;
+    let mut first_divergence: Option<usize> = None;
+    for (is_equal, group) in &runs {
+        if *is_equal {
+            continue;
+        }
+        if first_divergence.is_none() {
+            first_divergence = group.iter().find_map(|op| match op {
+                LineDiffOp::OnlyInActual(i) => Some(*i),
+                LineDiffOp::OnlyInExpected(j) => Some(*j),
+                LineDiffOp::Equal(..) => None,
+            });
+        }
+        let run_removed = group
+            .iter()
+            .filter(|op| matches!(op, LineDiffOp::OnlyInExpected(_)))
+            .count();
+        let run_added = group
+            .iter()
+            .filter(|op| matches!(op, LineDiffOp::OnlyInActual(_)))
+            .count();
+        let run_changed = run_removed.min(run_added);
+        changed += run_changed;
+        removed += run_removed - run_changed;
+        added += run_added - run_changed;
+    }
+
+    let mut out = format!("{added} added, {removed} removed, {changed} changed\n");
+    if let Some(index) = first_divergence {
+        out.push_str(&format!("first divergence at line {}\n", index + 1));
+    }
+
+    let run_count = runs.len();
+    for (run_index, (is_equal, group)) in runs.into_iter().enumerate() {
+        if is_equal {
+            let lead = if run_index == 0 {
+                0
+            } else {
+                DIFF_CONTEXT_LINES
+            };
+            let trail = if run_index + 1 == run_count {
+                0
+            } else {
+                DIFF_CONTEXT_LINES
+            };
+            let render_equal = |op: &LineDiffOp, out: &mut String| {
+                if let LineDiffOp::Equal(i) = op {
+                    out.push_str(&format!("  {}\n", actual_lines[*i]));
+                }
+            };
+            if group.len() <= lead + trail {
+                for op in &group {
+                    render_equal(op, &mut out);
+                }
+            } else {
+                for op in &group[..lead] {
+                    render_equal(op, &mut out);
+                }
+                out.push_str("  ...\n");
+                for op in &group[group.len() - trail..] {
+                    render_equal(op, &mut out);
+                }
+            }
+        } else {
+            for op in &group {
+                match op {
+                    LineDiffOp::OnlyInExpected(j) => {
+                        out.push_str(&format!("- {}\n", expected_lines[*j]))
+                    }
+                    LineDiffOp::OnlyInActual(i) => {
+                        out.push_str(&format!("+ {}\n", actual_lines[*i]))
+                    }
+                    LineDiffOp::Equal(..) => unreachable!(),
+                }
+            }
+        }
+    }
+
+    out
+}
+
 struct CollectSink {
     events: Vec<Value>,
 }
@@ -106,6 +343,115 @@ fn parse_partition_count_env() -> Result<Option<usize>, String> {
     Ok(Some(parsed))
 }
 
+fn parse_fixture_record_env() -> bool {
+    std::env::var("DIFFLY_FIXTURE_RECORD")
+        .map(|value| value.trim() == "1")
+        .unwrap_or(false)
+}
+
+fn record_error(
+    expected_error: &Path,
+    expected_jsonl: &Path,
+    err: &DiffError,
+) -> Result<(), String> {
+    if expected_jsonl.exists() {
+        fs::remove_file(expected_jsonl).map_err(|io_err| {
+            format!(
+                "failed to remove stale {}: {io_err}",
+                expected_jsonl.display()
+            )
+        })?;
+    }
+    let recorded = serde_json::json!({
+        "code": err.code,
+        "message_contains": err.message,
+    });
+    fs::write(
+        expected_error,
+        serde_json::to_string_pretty(&recorded).unwrap_or_else(|_| "{}".to_string()),
+    )
+    .map_err(|io_err| format!("failed to write {}: {io_err}", expected_error.display()))
+}
+
+fn record_events(
+    expected_jsonl: &Path,
+    expected_error: &Path,
+    events: &[Value],
+) -> Result<(), String> {
+    if expected_error.exists() {
+        fs::remove_file(expected_error).map_err(|io_err| {
+            format!(
+                "failed to remove stale {}: {io_err}",
+                expected_error.display()
+            )
+        })?;
+    }
+    let mut content = String::new();
+    for event in events {
+        content.push_str(&serde_json::to_string(event).map_err(|err| err.to_string())?);
+        content.push('\n');
+    }
+    fs::write(expected_jsonl, content)
+        .map_err(|io_err| format!("failed to write {}: {io_err}", expected_jsonl.display()))
+}
+
+fn message_contains_needles(expected: &Value) -> Vec<String> {
+    match expected.get("message_contains") {
+        Some(Value::String(needle)) if !needle.is_empty() => vec![needle.clone()],
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(ToString::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Checks an actual DiffError against an `expected_error.json` fixture, reporting
+// precisely which clause (code, a message_contains needle, message_matches, or
+// message_not_contains) failed.
+fn check_error_assertions(err: &DiffError, expected: &Value) -> Result<(), String> {
+    let expected_code = expected.get("code").and_then(Value::as_str).unwrap_or("");
+    if err.code != expected_code {
+        return Err(format!(
+            "error code mismatch: got {}, expected {}",
+            err.code, expected_code
+        ));
+    }
+
+    for needle in message_contains_needles(expected) {
+        if !err.message.contains(&needle) {
+            return Err(format!(
+                "error message mismatch: expected to contain '{needle}', got '{}'",
+                err.message
+            ));
+        }
+    }
+
+    if let Some(pattern) = expected.get("message_matches").and_then(Value::as_str) {
+        let matcher = Regex::new(pattern).map_err(|regex_err| {
+            format!("invalid message_matches regex '{pattern}': {regex_err}")
+        })?;
+        if !matcher.is_match(&err.message) {
+            return Err(format!(
+                "error message mismatch: expected to match /{pattern}/, got '{}'",
+                err.message
+            ));
+        }
+    }
+
+    if let Some(needle) = expected.get("message_not_contains").and_then(Value::as_str) {
+        if !needle.is_empty() && err.message.contains(needle) {
+            return Err(format!(
+                "error message mismatch: expected not to contain '{needle}', got '{}'",
+                err.message
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 fn run_diff(
     a_path: &Path,
     b_path: &Path,
@@ -146,7 +492,7 @@ fn run_diff(
     }
 }
 
-fn run_case(case_dir: &Path, partition_count: Option<usize>) -> (bool, String) {
+fn run_case(case_dir: &Path, partition_count: Option<usize>, record: bool) -> (bool, String) {
     let config_path = case_dir.join("config.json");
     if !config_path.exists() {
         return (true, "skipped (no config.json)".to_string());
@@ -173,7 +519,7 @@ fn run_case(case_dir: &Path, partition_count: Option<usize>) -> (bool, String) {
     let expected_jsonl = case_dir.join("expected.jsonl");
     let expected_error = case_dir.join("expected_error.json");
 
-    if expected_jsonl.exists() == expected_error.exists() {
+    if !record && expected_jsonl.exists() == expected_error.exists() {
         return (
             false,
             "fixture must include exactly one of expected.jsonl or expected_error.json".to_string(),
@@ -192,6 +538,13 @@ fn run_case(case_dir: &Path, partition_count: Option<usize>) -> (bool, String) {
 
     match actual {
         Err(err) => {
+            if record {
+                return match record_error(&expected_error, &expected_jsonl, &err) {
+                    Ok(()) => (true, "recorded expected_error.json".to_string()),
+                    Err(message) => (false, message),
+                };
+            }
+
             if !expected_error.exists() {
                 return (
                     false,
@@ -219,34 +572,19 @@ fn run_case(case_dir: &Path, partition_count: Option<usize>) -> (bool, String) {
                 }
             };
 
-            let expected_code = expected.get("code").and_then(Value::as_str).unwrap_or("");
-            if err.code != expected_code {
-                return (
-                    false,
-                    format!(
-                        "error code mismatch: got {}, expected {}",
-                        err.code, expected_code
-                    ),
-                );
-            }
-
-            let needle = expected
-                .get("message_contains")
-                .and_then(Value::as_str)
-                .unwrap_or("");
-            if !needle.is_empty() && !err.message.contains(needle) {
-                return (
-                    false,
-                    format!(
-                        "error message mismatch: expected to contain '{needle}', got '{}'",
-                        err.message
-                    ),
-                );
+            match check_error_assertions(&err, &expected) {
+                Ok(()) => (true, "ok".to_string()),
+                Err(message) => (false, message),
             }
-
-            (true, "ok".to_string())
         }
         Ok(events) => {
+            if record {
+                return match record_events(&expected_jsonl, &expected_error, &events) {
+                    Ok(()) => (true, "recorded expected.jsonl".to_string()),
+                    Err(message) => (false, message),
+                };
+            }
+
             if expected_error.exists() {
                 return (false, "expected error but case succeeded".to_string());
             }
@@ -256,15 +594,16 @@ fn run_case(case_dir: &Path, partition_count: Option<usize>) -> (bool, String) {
                 Err(err) => return (false, err),
             };
 
+            let normalize = parse_normalize_config(&config);
+            let events = normalize_events(&events, &normalize);
+            let expected = normalize_events(&expected, &normalize);
+
             if events != expected {
                 return (
                     false,
                     format!(
-                        "output mismatch\nactual:   {}\nexpected: {}",
-                        serde_json::to_string_pretty(&events)
-                            .unwrap_or_else(|_| "<serialize failed>".to_string()),
-                        serde_json::to_string_pretty(&expected)
-                            .unwrap_or_else(|_| "<serialize failed>".to_string())
+                        "output mismatch\n{}",
+                        render_events_diff(&events, &expected)
                     ),
                 );
             }
@@ -283,7 +622,211 @@ fn repo_root() -> PathBuf {
         .expect("failed to resolve repository root")
 }
 
+struct CliArgs {
+    filter: Option<String>,
+    exact: Option<String>,
+    list: bool,
+    mode: Option<String>,
+}
+
+fn cli_help_text() -> String {
+    [
+        "Usage:",
+        "  diffly-conformance [filter] [--exact name] [--list] [--mode keyed|positional]",
+        "",
+        "Options:",
+        "  [filter]            Substring filter on fixture case directory names",
+        "  --exact <name>      Run only the case with this exact name",
+        "  --list              Print discovered case names without running them",
+        "  --mode <mode>       Only run cases whose config.json mode matches: keyed | positional",
+    ]
+    .join("\n")
+}
+
+fn parse_cli_args() -> Result<CliArgs, String> {
+    let mut filter: Option<String> = None;
+    let mut exact: Option<String> = None;
+    let mut list = false;
+    let mut mode: Option<String> = None;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--exact" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--exact requires a value".to_string())?;
+                exact = Some(value.clone());
+            }
+            "--list" => list = true,
+            "--mode" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--mode requires a value".to_string())?;
+                if value != "keyed" && value != "positional" {
+                    return Err(format!(
+                        "--mode must be keyed or positional, got {value}\n\n{}",
+                        cli_help_text()
+                    ));
+                }
+                mode = Some(value.clone());
+            }
+            "-h" | "--help" => return Err(cli_help_text()),
+            positional if !positional.starts_with('-') => {
+                filter = Some(positional.to_string());
+            }
+            unknown => {
+                return Err(format!(
+                    "Unknown argument: {unknown}\n\n{}",
+                    cli_help_text()
+                ))
+            }
+        }
+        i += 1;
+    }
+
+    Ok(CliArgs {
+        filter,
+        exact,
+        list,
+        mode,
+    })
+}
+
+fn case_mode(case_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(case_dir.join("config.json")).ok()?;
+    let config: Value = serde_json::from_str(&content).ok()?;
+    Some(
+        config
+            .get("mode")
+            .and_then(Value::as_str)
+            .unwrap_or("keyed")
+            .to_string(),
+    )
+}
+
+fn case_name(case_dir: &Path) -> &str {
+    case_dir
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("<unknown>")
+}
+
+fn selected_case_dirs(fixtures_root: &Path, cli: &CliArgs) -> Vec<PathBuf> {
+    let mut case_dirs: Vec<PathBuf> = fs::read_dir(fixtures_root)
+        .expect("failed to read fixtures directory")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    case_dirs.sort();
+
+    case_dirs.retain(|case_dir| {
+        let name = case_name(case_dir);
+        if let Some(exact) = &cli.exact {
+            if name != exact {
+                return false;
+            }
+        } else if let Some(filter) = &cli.filter {
+            if !name.contains(filter.as_str()) {
+                return false;
+            }
+        }
+        if let Some(mode) = &cli.mode {
+            if case_mode(case_dir).as_deref() != Some(mode.as_str()) {
+                return false;
+            }
+        }
+        true
+    });
+
+    case_dirs
+}
+
+fn parse_fixture_jobs_env() -> Result<usize, String> {
+    let raw = match std::env::var("DIFFLY_FIXTURE_JOBS") {
+        Ok(value) => value,
+        Err(_) => return Ok(default_job_count()),
+    };
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(default_job_count());
+    }
+    let parsed = trimmed
+        .parse::<usize>()
+        .map_err(|_| format!("DIFFLY_FIXTURE_JOBS must be a positive integer, got '{trimmed}'"))?;
+    if parsed == 0 {
+        return Err("DIFFLY_FIXTURE_JOBS must be greater than zero".to_string());
+    }
+    Ok(parsed)
+}
+
+fn default_job_count() -> usize {
+    std::thread::available_parallelism()
+        .map(std::num::NonZeroUsize::get)
+        .unwrap_or(1)
+}
+
+struct CaseOutcome {
+    index: usize,
+    name: String,
+    ok: bool,
+    message: String,
+}
+
+// Dispatches `run_case` across a bounded pool of worker threads pulling from a
+// shared work queue, then sorts results back into the original case order so
+// output stays deterministic regardless of scheduling.
+fn run_cases_parallel(
+    case_dirs: &[PathBuf],
+    partition_count: Option<usize>,
+    record: bool,
+    jobs: usize,
+) -> Vec<CaseOutcome> {
+    let next_index = std::sync::atomic::AtomicUsize::new(0);
+    let results = std::sync::Mutex::new(Vec::with_capacity(case_dirs.len()));
+    let worker_count = jobs.min(case_dirs.len()).max(1);
+
+    std::thread::scope(|scope| {
+        for _ in 0..worker_count {
+            let next_index = &next_index;
+            let results = &results;
+            scope.spawn(move || loop {
+                let index = next_index.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let Some(case_dir) = case_dirs.get(index) else {
+                    break;
+                };
+                let name = case_name(case_dir).to_string();
+                let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    run_case(case_dir, partition_count, record)
+                }));
+                let (ok, message) = outcome
+                    .unwrap_or_else(|_| (false, format!("case '{name}' panicked while running")));
+                results.lock().unwrap().push(CaseOutcome {
+                    index,
+                    name,
+                    ok,
+                    message,
+                });
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|outcome| outcome.index);
+    results
+}
+
 fn main() {
+    let cli = match parse_cli_args() {
+        Ok(cli) => cli,
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(2);
+        }
+    };
     let partition_count = match parse_partition_count_env() {
         Ok(value) => value,
         Err(message) => {
@@ -291,32 +834,59 @@ fn main() {
             std::process::exit(2);
         }
     };
+    let record = parse_fixture_record_env();
+    let jobs = match parse_fixture_jobs_env() {
+        Ok(value) => value,
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(2);
+        }
+    };
 
     let root = repo_root();
     let fixtures_root = root.join("diffly-spec").join("fixtures");
-
-    let mut case_dirs: Vec<PathBuf> = fs::read_dir(&fixtures_root)
-        .expect("failed to read fixtures directory")
-        .filter_map(|entry| entry.ok().map(|e| e.path()))
-        .filter(|path| path.is_dir())
-        .collect();
-    case_dirs.sort();
+    let case_dirs = selected_case_dirs(&fixtures_root, &cli);
     let case_count = case_dirs.len();
 
+    if cli.list {
+        for case_dir in &case_dirs {
+            println!("{}", case_name(case_dir));
+        }
+        return;
+    }
+
+    let outcomes = run_cases_parallel(&case_dirs, partition_count, record, jobs);
+
     let mut failed = 0usize;
-    for case_dir in case_dirs {
-        let (ok, msg) = run_case(&case_dir, partition_count);
-        let status = if ok { "PASS" } else { "FAIL" };
-        let name = case_dir
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or("<unknown>");
-        println!("[{status}] {name}: {msg}");
-        if !ok {
+    let mut recorded_names: Vec<String> = Vec::new();
+    for outcome in &outcomes {
+        let status = if outcome.ok { "PASS" } else { "FAIL" };
+        println!("[{status}] {}: {}", outcome.name, outcome.message);
+        if outcome.message.starts_with("recorded ") {
+            recorded_names.push(outcome.name.clone());
+        }
+        if !outcome.ok {
             failed += 1;
         }
     }
 
+    if record {
+        if recorded_names.is_empty() {
+            println!("\nNo fixtures needed recording");
+        } else {
+            println!(
+                "\nRecorded {} fixture(s): {}",
+                recorded_names.len(),
+                recorded_names.join(", ")
+            );
+        }
+        if failed > 0 {
+            eprintln!("\n{failed} fixture(s) failed to record");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     if failed > 0 {
         eprintln!("\n{failed} fixture(s) failed");
         std::process::exit(1);
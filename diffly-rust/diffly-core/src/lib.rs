@@ -4,7 +4,11 @@ use std::fmt::{Display, Formatter};
 use std::io::Read;
 use std::path::Path;
 
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Timelike, Utc};
 use csv::ReaderBuilder;
+use rayon::prelude::*;
 use serde_json::{json, Map, Value};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,11 +30,257 @@ impl HeaderMode {
     }
 }
 
+/// Controls whether surrounding whitespace is stripped from header names,
+/// field values, both, or neither while parsing a CSV. Mirrors `csv::Trim`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CsvTrim {
+    #[default]
+    None,
+    Headers,
+    Fields,
+    All,
+}
+
+impl CsvTrim {
+    pub fn parse(value: &str) -> Result<Self, DiffError> {
+        match value {
+            "none" => Ok(Self::None),
+            "headers" => Ok(Self::Headers),
+            "fields" => Ok(Self::Fields),
+            "all" => Ok(Self::All),
+            other => Err(DiffError::new(
+                "invalid_trim_mode",
+                format!("Unsupported trim mode: {other}"),
+            )),
+        }
+    }
+}
+
+/// How a column's values should be parsed before comparison. Unlisted
+/// columns (or `String`) fall back to exact string equality.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Int,
+    Float,
+    Decimal,
+    Bool,
+    Timestamp,
+    String,
+}
+
+impl ColumnType {
+    pub fn parse(value: &str) -> Result<Self, DiffError> {
+        match value {
+            "int" => Ok(Self::Int),
+            "float" => Ok(Self::Float),
+            "decimal" => Ok(Self::Decimal),
+            "bool" => Ok(Self::Bool),
+            "timestamp" => Ok(Self::Timestamp),
+            "string" => Ok(Self::String),
+            other => Err(DiffError::new(
+                "invalid_column_type",
+                format!("Unsupported column type: {other}"),
+            )),
+        }
+    }
+}
+
+/// Absolute and relative tolerance applied when comparing `Float`/`Decimal`
+/// columns. Two values are equal if they fall within either bound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatTolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl Default for FloatTolerance {
+    fn default() -> Self {
+        Self {
+            absolute: 0.0,
+            relative: 0.0,
+        }
+    }
+}
+
+/// Precision `Timestamp` columns are truncated to before comparison, so
+/// e.g. millisecond-precision instants can be compared at second precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampGranularity {
+    #[default]
+    Nanosecond,
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+impl TimestampGranularity {
+    pub fn parse(value: &str) -> Result<Self, DiffError> {
+        match value {
+            "nanosecond" => Ok(Self::Nanosecond),
+            "microsecond" => Ok(Self::Microsecond),
+            "millisecond" => Ok(Self::Millisecond),
+            "second" => Ok(Self::Second),
+            "minute" => Ok(Self::Minute),
+            "hour" => Ok(Self::Hour),
+            "day" => Ok(Self::Day),
+            other => Err(DiffError::new(
+                "invalid_timestamp_granularity",
+                format!("Unsupported timestamp granularity: {other}"),
+            )),
+        }
+    }
+}
+
+/// Granularity at which a changed cell's old/new text is broken into tokens
+/// before being diffed. `None` skips cell-level diffing entirely (the
+/// default, and the allocation-light path).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FieldDiffMode {
+    #[default]
+    None,
+    Line,
+    Word,
+    Char,
+}
+
+impl FieldDiffMode {
+    pub fn parse(value: &str) -> Result<Self, DiffError> {
+        match value {
+            "none" => Ok(Self::None),
+            "line" => Ok(Self::Line),
+            "word" => Ok(Self::Word),
+            "char" => Ok(Self::Char),
+            other => Err(DiffError::new(
+                "invalid_field_diff_mode",
+                format!("Unsupported field diff mode: {other}"),
+            )),
+        }
+    }
+}
+
+/// Selects the emitted event shape: a diff-centric change feed, or a
+/// reconciliation-style join of A and B rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputMode {
+    #[default]
+    Diff,
+    Join,
+}
+
+impl OutputMode {
+    pub fn parse(value: &str) -> Result<Self, DiffError> {
+        match value {
+            "diff" => Ok(Self::Diff),
+            "join" => Ok(Self::Join),
+            other => Err(DiffError::new(
+                "invalid_output_mode",
+                format!("Unsupported output mode: {other}"),
+            )),
+        }
+    }
+}
+
+/// Which keys to include in `OutputMode::Join` output, analogous to
+/// `xsv join`'s `--left`/`--right`/`--inner`/`--full` flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JoinSelection {
+    Left,
+    Right,
+    Inner,
+    #[default]
+    Full,
+}
+
+impl JoinSelection {
+    pub fn parse(value: &str) -> Result<Self, DiffError> {
+        match value {
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "inner" => Ok(Self::Inner),
+            "full" => Ok(Self::Full),
+            other => Err(DiffError::new(
+                "invalid_join_selection",
+                format!("Unsupported join selection: {other}"),
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DiffOptions {
     pub key_columns: Vec<String>,
     pub header_mode: HeaderMode,
     pub emit_unchanged: bool,
+    /// Field delimiter byte. Defaults to `,`.
+    pub delimiter: u8,
+    /// Quote character byte. Defaults to `"`.
+    pub quote: u8,
+    /// Escape character for quoted fields, used when `double_quote` is false.
+    pub escape: Option<u8>,
+    /// Whether a quote may be escaped by doubling it (`""`). Defaults to true.
+    pub double_quote: bool,
+    /// Record terminator byte. `None` accepts CRLF or LF, matching today's behavior.
+    pub terminator: Option<u8>,
+    /// Whitespace trimming applied to headers and/or field values.
+    pub trim: CsvTrim,
+    /// When true, a row with fewer or more fields than the header is
+    /// tolerated: missing trailing fields read as empty strings, extra
+    /// trailing fields are ignored. When false (the default), any width
+    /// mismatch is a `row_width_mismatch` error.
+    pub flexible: bool,
+    /// Declared type for columns that should be compared value-aware rather
+    /// than as raw strings. Columns absent from this map compare as strings.
+    pub column_types: HashMap<String, ColumnType>,
+    /// Per-column tolerance for `Float`/`Decimal` columns. Columns absent
+    /// from this map use `FloatTolerance::default()` (exact match).
+    pub float_tolerance: HashMap<String, FloatTolerance>,
+    /// Per-column truncation precision for `Timestamp` columns. Columns
+    /// absent from this map use `TimestampGranularity::default()`.
+    pub timestamp_granularity: HashMap<String, TimestampGranularity>,
+    /// When true, columns missing from `column_types` have their type
+    /// guessed by sampling the first rows of both inputs.
+    pub infer_column_types: bool,
+    /// Token granularity for cell-level diffs attached to `changed` events.
+    /// Defaults to `FieldDiffMode::None`, which skips cell diffing.
+    pub field_diff: FieldDiffMode,
+    /// Cells (old or new) longer than this many bytes skip segment
+    /// computation and fall back to whole-value replacement, bounding the
+    /// LCS pass's memory use.
+    pub field_diff_max_len: usize,
+    /// When true, rows are read as raw bytes (`csv::ByteRecord`) and keyed
+    /// and compared as `Vec<u8>` instead of `String`, so files with
+    /// non-UTF-8 fields can be diffed without a parse error. Only lossy
+    /// UTF-8 conversion happens at the JSON boundary, where non-UTF-8 cells
+    /// are emitted as a `base64:`-prefixed string. Value-aware comparison
+    /// (`column_types`, `float_tolerance`, `timestamp_granularity`,
+    /// `field_diff`) is not available in this mode; cells compare as exact
+    /// byte sequences.
+    pub lossless_bytes: bool,
+    /// Worker threads used by `diff_rows_keyed` to compare matched rows in
+    /// parallel. `0` (the default) auto-detects from available parallelism.
+    pub jobs: usize,
+    /// When true, a `changed` event's `before`/`after` objects omit fields
+    /// that didn't change, keeping only the columns listed in `changed`
+    /// plus the key columns (so rows stay identifiable). Shrinks output
+    /// substantially for sparse changes across wide tables.
+    pub drop_equal_fields: bool,
+    /// When true (and no `key_columns` are set), the positional diff
+    /// compares A and B as multisets of whole rows instead of by position:
+    /// `min(count_a, count_b)` identical rows are unchanged, and any
+    /// surplus on either side is reported as `added`/`removed`. A
+    /// `changed` event is never emitted in this mode — a row either
+    /// matches some other row exactly, or it's added/removed.
+    pub ignore_row_order: bool,
+    /// Selects between the default diff-centric event feed and a
+    /// reconciliation-style join of A and B rows. `Join` requires
+    /// `key_columns` and reuses the keyed index, but emits `joined` events
+    /// instead of `added`/`removed`/`changed`/`unchanged`.
+    pub output_mode: OutputMode,
+    /// Which keys `OutputMode::Join` includes. Ignored in `OutputMode::Diff`.
+    pub join_selection: JoinSelection,
 }
 
 impl Default for DiffOptions {
@@ -39,6 +289,25 @@ impl Default for DiffOptions {
             key_columns: Vec::new(),
             header_mode: HeaderMode::Strict,
             emit_unchanged: false,
+            delimiter: b',',
+            quote: b'"',
+            escape: None,
+            double_quote: true,
+            terminator: None,
+            trim: CsvTrim::None,
+            flexible: false,
+            column_types: HashMap::new(),
+            float_tolerance: HashMap::new(),
+            timestamp_granularity: HashMap::new(),
+            infer_column_types: false,
+            field_diff: FieldDiffMode::None,
+            field_diff_max_len: 4096,
+            lossless_bytes: false,
+            jobs: 0,
+            drop_equal_fields: false,
+            ignore_row_order: false,
+            output_mode: OutputMode::Diff,
+            join_selection: JoinSelection::Full,
         }
     }
 }
@@ -68,6 +337,8 @@ impl Error for DiffError {}
 
 type Row = BTreeMap<String, String>;
 type IndexedRow = (usize, Row);
+type ByteRow = BTreeMap<String, Vec<u8>>;
+type IndexedByteRow = (usize, ByteRow);
 
 fn validate_header(header: &[String], side: &str) -> Result<(), DiffError> {
     let mut seen = HashSet::new();
@@ -90,14 +361,37 @@ fn normalize_header(header: &mut [String]) {
     }
 }
 
+fn dialect_terminator(options: &DiffOptions) -> csv::Terminator {
+    match options.terminator {
+        Some(byte) => csv::Terminator::Any(byte),
+        None => csv::Terminator::CRLF,
+    }
+}
+
+fn dialect_trim(options: &DiffOptions) -> csv::Trim {
+    match options.trim {
+        CsvTrim::None => csv::Trim::None,
+        CsvTrim::Headers => csv::Trim::Headers,
+        CsvTrim::Fields => csv::Trim::Fields,
+        CsvTrim::All => csv::Trim::All,
+    }
+}
+
 fn read_csv_reader<R: Read>(
     reader: R,
     side: &str,
     source_label: &str,
+    options: &DiffOptions,
 ) -> Result<(Vec<String>, Vec<IndexedRow>), DiffError> {
     let mut reader = ReaderBuilder::new()
         .has_headers(false)
         .flexible(true)
+        .delimiter(options.delimiter)
+        .quote(options.quote)
+        .escape(options.escape)
+        .double_quote(options.double_quote)
+        .terminator(dialect_terminator(options))
+        .trim(dialect_trim(options))
         .from_reader(reader);
 
     let mut records = reader.records();
@@ -128,7 +422,7 @@ fn read_csv_reader<R: Read>(
             )
         })?;
 
-        if record.len() != width {
+        if record.len() != width && !options.flexible {
             return Err(DiffError::new(
                 "row_width_mismatch",
                 format!(
@@ -139,8 +433,90 @@ fn read_csv_reader<R: Read>(
         }
 
         let mut row: Row = BTreeMap::new();
-        for (key, value) in header.iter().zip(record.iter()) {
-            row.insert(key.clone(), value.to_string());
+        for (idx, key) in header.iter().enumerate() {
+            row.insert(key.clone(), record.get(idx).unwrap_or_default().to_string());
+        }
+        rows.push((row_index, row));
+    }
+
+    Ok((header, rows))
+}
+
+fn read_csv(
+    path: &Path,
+    side: &str,
+    options: &DiffOptions,
+) -> Result<(Vec<String>, Vec<IndexedRow>), DiffError> {
+    let file = std::fs::File::open(path)
+        .map_err(|err| DiffError::new("csv_open_error", format!("Failed to open {side}: {err}")))?;
+    read_csv_reader(file, side, &path.display().to_string(), options)
+}
+
+fn read_csv_reader_raw<R: Read>(
+    reader: R,
+    side: &str,
+    source_label: &str,
+    options: &DiffOptions,
+) -> Result<(Vec<String>, Vec<IndexedByteRow>), DiffError> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(options.delimiter)
+        .quote(options.quote)
+        .escape(options.escape)
+        .double_quote(options.double_quote)
+        .terminator(dialect_terminator(options))
+        .trim(dialect_trim(options))
+        .from_reader(reader);
+
+    let mut records = reader.byte_records();
+    let header_record = match records.next() {
+        None => {
+            return Err(DiffError::new(
+                "empty_file",
+                format!("{side} file is empty: {source_label}"),
+            ))
+        }
+        Some(result) => result.map_err(|err| {
+            DiffError::new("csv_parse_error", format!("Failed to parse {side}: {err}"))
+        })?,
+    };
+
+    let mut header: Vec<String> = header_record
+        .iter()
+        .map(|field| String::from_utf8_lossy(field).into_owned())
+        .collect();
+    normalize_header(&mut header);
+    // With `has_headers(false)`, csv's `Trim::Headers` never reaches this row.
+    if matches!(options.trim, CsvTrim::Headers | CsvTrim::All) {
+        header.iter_mut().for_each(|name| *name = name.trim().to_string());
+    }
+    validate_header(&header, side)?;
+
+    let width = header.len();
+    let mut rows: Vec<IndexedByteRow> = Vec::new();
+    for (idx, result) in records.enumerate() {
+        let row_index = idx + 2;
+        let record = result.map_err(|err| {
+            DiffError::new(
+                "csv_parse_error",
+                format!("Failed to parse {side} at CSV row {row_index}: {err}"),
+            )
+        })?;
+
+        if record.len() != width && !options.flexible {
+            return Err(DiffError::new(
+                "row_width_mismatch",
+                format!(
+                    "Row width mismatch in {side} at CSV row {row_index}: expected {width}, got {}",
+                    record.len()
+                ),
+            ));
+        }
+
+        let mut row: ByteRow = BTreeMap::new();
+        for (idx, key) in header.iter().enumerate() {
+            row.insert(key.clone(), record.get(idx).unwrap_or_default().to_vec());
         }
         rows.push((row_index, row));
     }
@@ -148,10 +524,14 @@ fn read_csv_reader<R: Read>(
     Ok((header, rows))
 }
 
-fn read_csv(path: &Path, side: &str) -> Result<(Vec<String>, Vec<IndexedRow>), DiffError> {
+fn read_csv_raw(
+    path: &Path,
+    side: &str,
+    options: &DiffOptions,
+) -> Result<(Vec<String>, Vec<IndexedByteRow>), DiffError> {
     let file = std::fs::File::open(path)
         .map_err(|err| DiffError::new("csv_open_error", format!("Failed to open {side}: {err}")))?;
-    read_csv_reader(file, side, &path.display().to_string())
+    read_csv_reader_raw(file, side, &path.display().to_string(), options)
 }
 
 fn comparison_columns(
@@ -249,143 +629,1502 @@ fn row_to_value(row: &Row) -> Value {
     Value::Object(value)
 }
 
-fn diff_rows_keyed(
-    a_header: Vec<String>,
-    a_rows: Vec<IndexedRow>,
-    b_header: Vec<String>,
-    b_rows: Vec<IndexedRow>,
-    options: &DiffOptions,
-) -> Result<Vec<Value>, DiffError> {
-    let compare_columns = comparison_columns(&a_header, &b_header, options.header_mode)?;
+/// Like `row_to_value`, but keeps only the changed columns and the key
+/// columns, dropping everything else. Used for `before`/`after` when
+/// `DiffOptions.drop_equal_fields` is set.
+fn row_to_value_changed_only(
+    row: &Row,
+    changed_columns: &[String],
+    key_columns: &[String],
+) -> Value {
+    let mut value = Map::new();
+    for column in changed_columns.iter().chain(key_columns) {
+        if let Some(val) = row.get(column) {
+            value.insert(column.clone(), Value::String(val.clone()));
+        }
+    }
+    Value::Object(value)
+}
 
-    for key_column in &options.key_columns {
-        if !a_header.contains(key_column) || !b_header.contains(key_column) {
+/// Renders a raw CSV cell for JSON output: valid UTF-8 bytes pass through as
+/// a plain string; invalid UTF-8 is base64-encoded and `base64:`-prefixed so
+/// consumers can tell the two apart.
+fn byte_cell_to_value(bytes: &[u8]) -> Value {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Value::String(text.to_string()),
+        Err(_) => Value::String(format!("base64:{}", BASE64.encode(bytes))),
+    }
+}
+
+fn key_tuple_raw(row: &ByteRow, key_columns: &[String]) -> Vec<Vec<u8>> {
+    key_columns
+        .iter()
+        .map(|column| row.get(column).cloned().unwrap_or_default())
+        .collect()
+}
+
+fn key_object_raw(key_columns: &[String], key_tuple_value: &[Vec<u8>]) -> Value {
+    let mut key = Map::new();
+    for (idx, column) in key_columns.iter().enumerate() {
+        key.insert(column.clone(), byte_cell_to_value(&key_tuple_value[idx]));
+    }
+    Value::Object(key)
+}
+
+fn index_rows_raw(
+    rows: Vec<IndexedByteRow>,
+    key_columns: &[String],
+    side: &str,
+) -> Result<HashMap<Vec<Vec<u8>>, IndexedByteRow>, DiffError> {
+    let mut indexed: HashMap<Vec<Vec<u8>>, IndexedByteRow> = HashMap::new();
+    for (row_index, row) in rows {
+        for key_column in key_columns {
+            let value = row.get(key_column).ok_or_else(|| {
+                DiffError::new(
+                    "missing_key_column",
+                    format!("Missing key column: {key_column}"),
+                )
+            })?;
+            if value.is_empty() {
+                return Err(DiffError::new(
+                    "missing_key_value",
+                    format!(
+                        "Missing key value in {side} at CSV row {row_index} for key column '{key_column}'"
+                    ),
+                ));
+            }
+        }
+
+        let key = key_tuple_raw(&row, key_columns);
+        if let Some((prior_row, _)) = indexed.get(&key) {
             return Err(DiffError::new(
-                "missing_key_column",
-                format!("Missing key column: {key_column}"),
+                "duplicate_key",
+                format!(
+                    "Duplicate key in {side}: {} (rows {} and {})",
+                    key_object_raw(key_columns, &key),
+                    prior_row,
+                    row_index
+                ),
             ));
         }
+        indexed.insert(key, (row_index, row));
     }
+    Ok(indexed)
+}
 
-    let indexed_a = index_rows(a_rows, &options.key_columns, "A")?;
-    let indexed_b = index_rows(b_rows, &options.key_columns, "B")?;
+fn row_to_value_raw(row: &ByteRow) -> Value {
+    let mut value = Map::new();
+    for (key, val) in row {
+        value.insert(key.clone(), byte_cell_to_value(val));
+    }
+    Value::Object(value)
+}
 
-    let mut all_keys: Vec<Vec<String>> = indexed_a
-        .keys()
-        .chain(indexed_b.keys())
-        .cloned()
-        .collect::<HashSet<_>>()
-        .into_iter()
-        .collect();
-    all_keys.sort();
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
 
-    let mut events: Vec<Value> = Vec::new();
-    events.push(json!({
-        "type": "schema",
-        "columns_a": a_header,
-        "columns_b": b_header
-    }));
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let trimmed = value.trim();
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(trimmed) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|naive| Utc.from_utc_datetime(&naive))
+}
 
-    let mut rows_total_compared = 0u64;
-    let mut rows_added = 0u64;
-    let mut rows_removed = 0u64;
-    let mut rows_changed = 0u64;
-    let mut rows_unchanged = 0u64;
+fn truncate_timestamp(instant: DateTime<Utc>, granularity: TimestampGranularity) -> DateTime<Utc> {
+    match granularity {
+        TimestampGranularity::Nanosecond => instant,
+        TimestampGranularity::Microsecond => instant
+            .with_nanosecond((instant.nanosecond() / 1_000) * 1_000)
+            .unwrap_or(instant),
+        TimestampGranularity::Millisecond => instant
+            .with_nanosecond((instant.nanosecond() / 1_000_000) * 1_000_000)
+            .unwrap_or(instant),
+        TimestampGranularity::Second => instant.with_nanosecond(0).unwrap_or(instant),
+        TimestampGranularity::Minute => instant
+            .with_nanosecond(0)
+            .and_then(|truncated| truncated.with_second(0))
+            .unwrap_or(instant),
+        TimestampGranularity::Hour => instant
+            .with_nanosecond(0)
+            .and_then(|truncated| truncated.with_second(0))
+            .and_then(|truncated| truncated.with_minute(0))
+            .unwrap_or(instant),
+        TimestampGranularity::Day => instant
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .map(|naive| Utc.from_utc_datetime(&naive))
+            .unwrap_or(instant),
+    }
+}
 
-    for key in all_keys {
-        let key_obj = key_object(&options.key_columns, &key);
-        let in_a = indexed_a.get(&key);
-        let in_b = indexed_b.get(&key);
+fn floats_within_tolerance(a: f64, b: f64, tolerance: FloatTolerance) -> bool {
+    let diff = (a - b).abs();
+    if diff <= tolerance.absolute {
+        return true;
+    }
+    let scale = a.abs().max(b.abs());
+    scale > 0.0 && diff <= tolerance.relative * scale
+}
 
-        match (in_a, in_b) {
-            (None, Some((_, row_b))) => {
-                rows_added += 1;
-                events.push(json!({
-                    "type": "added",
-                    "key": key_obj,
-                    "row": row_to_value(row_b)
-                }));
+/// Compares two raw cell values according to `column_type`, falling back to
+/// exact string equality (checked first, before any parsing) when the
+/// declared type fails to parse either side.
+pub fn values_equal(
+    column: &str,
+    a: &str,
+    b: &str,
+    column_type: Option<ColumnType>,
+    options: &DiffOptions,
+) -> bool {
+    if a == b {
+        return true;
+    }
+    match column_type {
+        Some(ColumnType::Int) => match (a.trim().parse::<i64>(), b.trim().parse::<i64>()) {
+            (Ok(x), Ok(y)) => x == y,
+            _ => false,
+        },
+        Some(ColumnType::Float) | Some(ColumnType::Decimal) => {
+            match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+                (Ok(x), Ok(y)) => {
+                    let tolerance = options
+                        .float_tolerance
+                        .get(column)
+                        .copied()
+                        .unwrap_or_default();
+                    floats_within_tolerance(x, y, tolerance)
+                }
+                _ => false,
             }
-            (Some((_, row_a)), None) => {
-                rows_removed += 1;
-                events.push(json!({
-                    "type": "removed",
-                    "key": key_obj,
-                    "row": row_to_value(row_a)
+        }
+        Some(ColumnType::Bool) => match (parse_bool(a), parse_bool(b)) {
+            (Some(x), Some(y)) => x == y,
+            _ => false,
+        },
+        Some(ColumnType::Timestamp) => match (parse_timestamp(a), parse_timestamp(b)) {
+            (Some(x), Some(y)) => {
+                let granularity = options
+                    .timestamp_granularity
+                    .get(column)
+                    .copied()
+                    .unwrap_or_default();
+                truncate_timestamp(x, granularity) == truncate_timestamp(y, granularity)
+            }
+            _ => false,
+        },
+        Some(ColumnType::String) | None => false,
+    }
+}
+
+/// Number of leading rows sampled from each side when inferring a column's
+/// type (`DiffOptions.infer_column_types`).
+const TYPE_INFERENCE_SAMPLE_ROWS: usize = 20;
+
+fn infer_column_type(samples: &[&str]) -> Option<ColumnType> {
+    if samples.is_empty() {
+        return None;
+    }
+    if samples.iter().all(|value| parse_bool(value).is_some()) {
+        return Some(ColumnType::Bool);
+    }
+    if samples
+        .iter()
+        .all(|value| value.trim().parse::<i64>().is_ok())
+    {
+        return Some(ColumnType::Int);
+    }
+    if samples
+        .iter()
+        .all(|value| value.trim().parse::<f64>().is_ok())
+    {
+        return Some(ColumnType::Float);
+    }
+    if samples.iter().all(|value| parse_timestamp(value).is_some()) {
+        return Some(ColumnType::Timestamp);
+    }
+    None
+}
+
+fn effective_column_types(
+    compare_columns: &[String],
+    a_rows: &[IndexedRow],
+    b_rows: &[IndexedRow],
+    options: &DiffOptions,
+) -> HashMap<String, ColumnType> {
+    let mut resolved = options.column_types.clone();
+    if !options.infer_column_types {
+        return resolved;
+    }
+
+    for column in compare_columns {
+        if resolved.contains_key(column) {
+            continue;
+        }
+        let samples: Vec<&str> = a_rows
+            .iter()
+            .take(TYPE_INFERENCE_SAMPLE_ROWS)
+            .chain(b_rows.iter().take(TYPE_INFERENCE_SAMPLE_ROWS))
+            .filter_map(|(_, row)| row.get(column).map(String::as_str))
+            .collect();
+        if let Some(inferred) = infer_column_type(&samples) {
+            resolved.insert(column.clone(), inferred);
+        }
+    }
+    resolved
+}
+
+fn tokenize_for_field_diff(text: &str, mode: FieldDiffMode) -> Vec<String> {
+    match mode {
+        FieldDiffMode::None => vec![text.to_string()],
+        FieldDiffMode::Line => text.split_inclusive('\n').map(str::to_string).collect(),
+        FieldDiffMode::Char => text.chars().map(|ch| ch.to_string()).collect(),
+        FieldDiffMode::Word => {
+            let mut tokens = Vec::new();
+            let mut current = String::new();
+            let mut current_is_whitespace = None;
+            for ch in text.chars() {
+                let is_whitespace = ch.is_whitespace();
+                if current_is_whitespace != Some(is_whitespace) && !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                current.push(ch);
+                current_is_whitespace = Some(is_whitespace);
+            }
+            if !current.is_empty() {
+                tokens.push(current);
+            }
+            tokens
+        }
+    }
+}
+
+/// Longest-common-subsequence diff over `from_tokens`/`to_tokens`, merging
+/// consecutive same-op tokens into a single segment.
+fn lcs_segments(from_tokens: &[String], to_tokens: &[String]) -> Vec<Value> {
+    let n = from_tokens.len();
+    let m = to_tokens.len();
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if from_tokens[i] == to_tokens[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    fn push_segment(segments: &mut Vec<Value>, op: &'static str, text: &str) {
+        if let Some(last) = segments.last_mut() {
+            if last.get("op").and_then(Value::as_str) == Some(op) {
+                if let Some(existing) = last.get("text").and_then(Value::as_str) {
+                    let merged = format!("{existing}{text}");
+                    *last = json!({ "op": op, "text": merged });
+                    return;
+                }
+            }
+        }
+        segments.push(json!({ "op": op, "text": text }));
+    }
+
+    let mut segments: Vec<Value> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if from_tokens[i] == to_tokens[j] {
+            push_segment(&mut segments, "equal", &from_tokens[i]);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            push_segment(&mut segments, "delete", &from_tokens[i]);
+            i += 1;
+        } else {
+            push_segment(&mut segments, "insert", &to_tokens[j]);
+            j += 1;
+        }
+    }
+    while i < n {
+        push_segment(&mut segments, "delete", &from_tokens[i]);
+        i += 1;
+    }
+    while j < m {
+        push_segment(&mut segments, "insert", &to_tokens[j]);
+        j += 1;
+    }
+    segments
+}
+
+/// Computes cell-level diff segments for one changed column's old/new text,
+/// or `None` when `mode` is `FieldDiffMode::None` or either side exceeds
+/// `max_len` (in which case the caller should fall back to whole-value
+/// replacement).
+pub fn field_diff_segments(
+    mode: FieldDiffMode,
+    from: &str,
+    to: &str,
+    max_len: usize,
+) -> Option<Vec<Value>> {
+    if mode == FieldDiffMode::None || from.len() > max_len || to.len() > max_len {
+        return None;
+    }
+    let from_tokens = tokenize_for_field_diff(from, mode);
+    let to_tokens = tokenize_for_field_diff(to, mode);
+    Some(lcs_segments(&from_tokens, &to_tokens))
+}
+
+fn build_delta(
+    changed_columns: &[String],
+    row_a: &Row,
+    row_b: &Row,
+    options: &DiffOptions,
+) -> Map<String, Value> {
+    let mut delta = Map::new();
+    for column in changed_columns {
+        let from = row_a.get(column).cloned().unwrap_or_default();
+        let to = row_b.get(column).cloned().unwrap_or_default();
+        let mut entry = json!({ "from": from, "to": to });
+        if let Some(segments) =
+            field_diff_segments(options.field_diff, &from, &to, options.field_diff_max_len)
+        {
+            entry["segments"] = Value::Array(segments);
+        }
+        delta.insert(column.clone(), entry);
+    }
+    delta
+}
+
+fn build_delta_raw(
+    changed_columns: &[String],
+    row_a: &ByteRow,
+    row_b: &ByteRow,
+) -> Map<String, Value> {
+    let mut delta = Map::new();
+    for column in changed_columns {
+        let from = row_a.get(column).cloned().unwrap_or_default();
+        let to = row_b.get(column).cloned().unwrap_or_default();
+        delta.insert(
+            column.clone(),
+            json!({ "from": byte_cell_to_value(&from), "to": byte_cell_to_value(&to) }),
+        );
+    }
+    delta
+}
+
+/// Per-key row counts produced by one `diff_rows_keyed` worker, combined
+/// across workers via `RowCounts::merge` to accumulate the `stats` event.
+#[derive(Default)]
+struct RowCounts {
+    total_compared: u64,
+    added: u64,
+    removed: u64,
+    changed: u64,
+    unchanged: u64,
+}
+
+impl RowCounts {
+    fn merge(mut self, other: RowCounts) -> RowCounts {
+        self.total_compared += other.total_compared;
+        self.added += other.added;
+        self.removed += other.removed;
+        self.changed += other.changed;
+        self.unchanged += other.unchanged;
+        self
+    }
+}
+
+/// Resolves `DiffOptions.jobs` to a concrete worker count: `0` auto-detects
+/// from available parallelism, falling back to a single thread if that
+/// can't be determined.
+fn effective_jobs(jobs: usize) -> usize {
+    if jobs == 0 {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    } else {
+        jobs
+    }
+}
+
+fn diff_rows_keyed(
+    a_header: Vec<String>,
+    a_rows: Vec<IndexedRow>,
+    b_header: Vec<String>,
+    b_rows: Vec<IndexedRow>,
+    options: &DiffOptions,
+) -> Result<Vec<Value>, DiffError> {
+    let compare_columns = comparison_columns(&a_header, &b_header, options.header_mode)?;
+
+    for key_column in &options.key_columns {
+        if !a_header.contains(key_column) || !b_header.contains(key_column) {
+            return Err(DiffError::new(
+                "missing_key_column",
+                format!("Missing key column: {key_column}"),
+            ));
+        }
+    }
+
+    let column_types = effective_column_types(&compare_columns, &a_rows, &b_rows, options);
+
+    let indexed_a = index_rows(a_rows, &options.key_columns, "A")?;
+    let indexed_b = index_rows(b_rows, &options.key_columns, "B")?;
+
+    let mut all_keys: Vec<Vec<String>> = indexed_a
+        .keys()
+        .chain(indexed_b.keys())
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    all_keys.sort();
+
+    let mut events: Vec<Value> = Vec::new();
+    events.push(json!({
+        "type": "schema",
+        "columns_a": a_header,
+        "columns_b": b_header
+    }));
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(effective_jobs(options.jobs))
+        .build()
+        .map_err(|err| {
+            DiffError::new(
+                "thread_pool_error",
+                format!("Failed to build diff thread pool: {err}"),
+            )
+        })?;
+
+    let (row_events, counts): (Vec<Value>, RowCounts) = pool.install(|| {
+        all_keys
+            .par_iter()
+            .map(|key| -> (Option<Value>, RowCounts) {
+                let key_obj = key_object(&options.key_columns, key);
+                let in_a = indexed_a.get(key);
+                let in_b = indexed_b.get(key);
+
+                match (in_a, in_b) {
+                    (None, Some((_, row_b))) => (
+                        Some(json!({
+                            "type": "added",
+                            "key": key_obj,
+                            "row": row_to_value(row_b)
+                        })),
+                        RowCounts {
+                            added: 1,
+                            ..Default::default()
+                        },
+                    ),
+                    (Some((_, row_a)), None) => (
+                        Some(json!({
+                            "type": "removed",
+                            "key": key_obj,
+                            "row": row_to_value(row_a)
+                        })),
+                        RowCounts {
+                            removed: 1,
+                            ..Default::default()
+                        },
+                    ),
+                    (Some((_, row_a)), Some((_, row_b))) => {
+                        let changed_columns: Vec<String> = compare_columns
+                            .iter()
+                            .filter(|column| {
+                                let value_a =
+                                    row_a.get(*column).map(String::as_str).unwrap_or_default();
+                                let value_b =
+                                    row_b.get(*column).map(String::as_str).unwrap_or_default();
+                                !values_equal(
+                                    column,
+                                    value_a,
+                                    value_b,
+                                    column_types.get(*column).copied(),
+                                    options,
+                                )
+                            })
+                            .cloned()
+                            .collect();
+
+                        if changed_columns.is_empty() {
+                            let event = options.emit_unchanged.then(|| {
+                                json!({
+                                    "type": "unchanged",
+                                    "key": key_obj,
+                                    "row": row_to_value(row_a)
+                                })
+                            });
+                            (
+                                event,
+                                RowCounts {
+                                    total_compared: 1,
+                                    unchanged: 1,
+                                    ..Default::default()
+                                },
+                            )
+                        } else {
+                            let delta = build_delta(&changed_columns, row_a, row_b, options);
+                            let (before, after) = if options.drop_equal_fields {
+                                (
+                                    row_to_value_changed_only(
+                                        row_a,
+                                        &changed_columns,
+                                        &options.key_columns,
+                                    ),
+                                    row_to_value_changed_only(
+                                        row_b,
+                                        &changed_columns,
+                                        &options.key_columns,
+                                    ),
+                                )
+                            } else {
+                                (row_to_value(row_a), row_to_value(row_b))
+                            };
+                            (
+                                Some(json!({
+                                    "type": "changed",
+                                    "key": key_obj,
+                                    "changed": changed_columns,
+                                    "before": before,
+                                    "after": after,
+                                    "delta": Value::Object(delta)
+                                })),
+                                RowCounts {
+                                    total_compared: 1,
+                                    changed: 1,
+                                    ..Default::default()
+                                },
+                            )
+                        }
+                    }
+                    (None, None) => (None, RowCounts::default()),
+                }
+            })
+            .fold(
+                || (Vec::new(), RowCounts::default()),
+                |(mut events, counts), (event, row_counts)| {
+                    if let Some(event) = event {
+                        events.push(event);
+                    }
+                    (events, counts.merge(row_counts))
+                },
+            )
+            .reduce(
+                || (Vec::new(), RowCounts::default()),
+                |(mut left_events, left_counts), (right_events, right_counts)| {
+                    left_events.extend(right_events);
+                    (left_events, left_counts.merge(right_counts))
+                },
+            )
+    });
+
+    events.extend(row_events);
+
+    events.push(json!({
+        "type": "stats",
+        "rows_total_compared": counts.total_compared,
+        "rows_added": counts.added,
+        "rows_removed": counts.removed,
+        "rows_changed": counts.changed,
+        "rows_unchanged": counts.unchanged
+    }));
+
+    Ok(events)
+}
+
+/// Canonical representation of a row's comparison-column values, in column
+/// order, used as the multiset key for `ignore_row_order` diffing.
+fn canonical_row_key(row: &Row, compare_columns: &[String]) -> String {
+    compare_columns
+        .iter()
+        .map(|column| row.get(column).map(String::as_str).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\u{1f}")
+}
+
+fn hash_canonical_row_key(canonical: &str) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    canonical.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Groups row positions by a stable hash of their canonical content, so
+/// equal rows can be counted without an all-pairs comparison. Each entry
+/// also keeps the canonical string itself, so hash collisions between
+/// distinct rows can be told apart by falling back to a direct comparison.
+fn build_row_multiset(
+    rows: &[IndexedRow],
+    compare_columns: &[String],
+) -> HashMap<u64, Vec<(String, usize)>> {
+    let mut multiset: HashMap<u64, Vec<(String, usize)>> = HashMap::new();
+    for (position, (_, row)) in rows.iter().enumerate() {
+        let canonical = canonical_row_key(row, compare_columns);
+        let hash = hash_canonical_row_key(&canonical);
+        multiset
+            .entry(hash)
+            .or_default()
+            .push((canonical, position));
+    }
+    multiset
+}
+
+/// Set-diff semantics for `ignore_row_order`: rows are compared as a
+/// multiset rather than by position. `min(count_a, count_b)` rows with
+/// identical content are unchanged; surplus rows on either side are
+/// reported as `added`/`removed`. A `changed` event is never produced.
+fn diff_rows_positional_unordered(
+    a_header: Vec<String>,
+    a_rows: Vec<IndexedRow>,
+    b_header: Vec<String>,
+    b_rows: Vec<IndexedRow>,
+    options: &DiffOptions,
+) -> Result<Vec<Value>, DiffError> {
+    let compare_columns = comparison_columns(&a_header, &b_header, options.header_mode)?;
+
+    let mut events: Vec<Value> = Vec::new();
+    events.push(json!({
+        "type": "schema",
+        "columns_a": a_header,
+        "columns_b": b_header
+    }));
+
+    let multiset_a = build_row_multiset(&a_rows, &compare_columns);
+    let multiset_b = build_row_multiset(&b_rows, &compare_columns);
+
+    let mut all_hashes: Vec<u64> = multiset_a
+        .keys()
+        .chain(multiset_b.keys())
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    all_hashes.sort_unstable();
+
+    let empty: Vec<(String, usize)> = Vec::new();
+    let mut rows_added = 0u64;
+    let mut rows_removed = 0u64;
+    let mut rows_unchanged = 0u64;
+
+    for hash in all_hashes {
+        let entries_a = multiset_a.get(&hash).unwrap_or(&empty);
+        let entries_b = multiset_b.get(&hash).unwrap_or(&empty);
+
+        let mut canonicals: Vec<&String> = entries_a
+            .iter()
+            .chain(entries_b.iter())
+            .map(|(canonical, _)| canonical)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        canonicals.sort();
+
+        for canonical in canonicals {
+            let mut positions_a: Vec<usize> = entries_a
+                .iter()
+                .filter(|(entry_canonical, _)| entry_canonical == canonical)
+                .map(|(_, position)| *position)
+                .collect();
+            let mut positions_b: Vec<usize> = entries_b
+                .iter()
+                .filter(|(entry_canonical, _)| entry_canonical == canonical)
+                .map(|(_, position)| *position)
+                .collect();
+            positions_a.sort_unstable();
+            positions_b.sort_unstable();
+
+            let matched = positions_a.len().min(positions_b.len());
+            rows_unchanged += matched as u64;
+            if options.emit_unchanged {
+                for &position in &positions_a[..matched] {
+                    let (row_index, row) = &a_rows[position];
+                    events.push(json!({
+                        "type": "unchanged",
+                        "row_index": row_index,
+                        "row": row_to_value(row)
+                    }));
+                }
+            }
+
+            for &position in &positions_b[matched..] {
+                rows_added += 1;
+                let (row_index, row) = &b_rows[position];
+                events.push(json!({
+                    "type": "added",
+                    "row_index": row_index,
+                    "row": row_to_value(row)
+                }));
+            }
+            for &position in &positions_a[matched..] {
+                rows_removed += 1;
+                let (row_index, row) = &a_rows[position];
+                events.push(json!({
+                    "type": "removed",
+                    "row_index": row_index,
+                    "row": row_to_value(row)
+                }));
+            }
+        }
+    }
+
+    events.push(json!({
+        "type": "stats",
+        "rows_total_compared": rows_unchanged,
+        "rows_added": rows_added,
+        "rows_removed": rows_removed,
+        "rows_changed": 0,
+        "rows_unchanged": rows_unchanged
+    }));
+
+    Ok(events)
+}
+
+fn diff_rows_positional(
+    a_header: Vec<String>,
+    a_rows: Vec<IndexedRow>,
+    b_header: Vec<String>,
+    b_rows: Vec<IndexedRow>,
+    options: &DiffOptions,
+) -> Result<Vec<Value>, DiffError> {
+    if options.ignore_row_order {
+        return diff_rows_positional_unordered(a_header, a_rows, b_header, b_rows, options);
+    }
+
+    let compare_columns = comparison_columns(&a_header, &b_header, options.header_mode)?;
+    let column_types = effective_column_types(&compare_columns, &a_rows, &b_rows, options);
+
+    let mut events: Vec<Value> = Vec::new();
+    events.push(json!({
+        "type": "schema",
+        "columns_a": a_header,
+        "columns_b": b_header
+    }));
+
+    let mut rows_total_compared = 0u64;
+    let mut rows_added = 0u64;
+    let mut rows_removed = 0u64;
+    let mut rows_changed = 0u64;
+    let mut rows_unchanged = 0u64;
+
+    let total_rows = a_rows.len().max(b_rows.len());
+    for idx in 0..total_rows {
+        let row_index = idx + 2;
+        let in_a = a_rows.get(idx);
+        let in_b = b_rows.get(idx);
+
+        match (in_a, in_b) {
+            (None, Some((_, row_b))) => {
+                rows_added += 1;
+                events.push(json!({
+                    "type": "added",
+                    "row_index": row_index,
+                    "row": row_to_value(row_b)
+                }));
+            }
+            (Some((_, row_a)), None) => {
+                rows_removed += 1;
+                events.push(json!({
+                    "type": "removed",
+                    "row_index": row_index,
+                    "row": row_to_value(row_a)
+                }));
+            }
+            (Some((_, row_a)), Some((_, row_b))) => {
+                rows_total_compared += 1;
+                let changed_columns: Vec<String> = compare_columns
+                    .iter()
+                    .filter(|column| {
+                        let value_a = row_a.get(*column).map(String::as_str).unwrap_or_default();
+                        let value_b = row_b.get(*column).map(String::as_str).unwrap_or_default();
+                        !values_equal(
+                            column,
+                            value_a,
+                            value_b,
+                            column_types.get(*column).copied(),
+                            options,
+                        )
+                    })
+                    .cloned()
+                    .collect();
+
+                if changed_columns.is_empty() {
+                    rows_unchanged += 1;
+                    if options.emit_unchanged {
+                        events.push(json!({
+                            "type": "unchanged",
+                            "row_index": row_index,
+                            "row": row_to_value(row_a)
+                        }));
+                    }
+                } else {
+                    rows_changed += 1;
+                    let delta = build_delta(&changed_columns, row_a, row_b, options);
+                    let (before, after) = if options.drop_equal_fields {
+                        (
+                            row_to_value_changed_only(
+                                row_a,
+                                &changed_columns,
+                                &options.key_columns,
+                            ),
+                            row_to_value_changed_only(
+                                row_b,
+                                &changed_columns,
+                                &options.key_columns,
+                            ),
+                        )
+                    } else {
+                        (row_to_value(row_a), row_to_value(row_b))
+                    };
+                    events.push(json!({
+                        "type": "changed",
+                        "row_index": row_index,
+                        "changed": changed_columns,
+                        "before": before,
+                        "after": after,
+                        "delta": Value::Object(delta)
+                    }));
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    events.push(json!({
+        "type": "stats",
+        "rows_total_compared": rows_total_compared,
+        "rows_added": rows_added,
+        "rows_removed": rows_removed,
+        "rows_changed": rows_changed,
+        "rows_unchanged": rows_unchanged
+    }));
+
+    Ok(events)
+}
+
+fn diff_rows_keyed_raw(
+    a_header: Vec<String>,
+    a_rows: Vec<IndexedByteRow>,
+    b_header: Vec<String>,
+    b_rows: Vec<IndexedByteRow>,
+    options: &DiffOptions,
+) -> Result<Vec<Value>, DiffError> {
+    let compare_columns = comparison_columns(&a_header, &b_header, options.header_mode)?;
+
+    for key_column in &options.key_columns {
+        if !a_header.contains(key_column) || !b_header.contains(key_column) {
+            return Err(DiffError::new(
+                "missing_key_column",
+                format!("Missing key column: {key_column}"),
+            ));
+        }
+    }
+
+    let indexed_a = index_rows_raw(a_rows, &options.key_columns, "A")?;
+    let indexed_b = index_rows_raw(b_rows, &options.key_columns, "B")?;
+
+    let mut all_keys: Vec<Vec<Vec<u8>>> = indexed_a
+        .keys()
+        .chain(indexed_b.keys())
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    all_keys.sort();
+
+    let mut events: Vec<Value> = Vec::new();
+    events.push(json!({
+        "type": "schema",
+        "columns_a": a_header,
+        "columns_b": b_header
+    }));
+
+    let mut rows_total_compared = 0u64;
+    let mut rows_added = 0u64;
+    let mut rows_removed = 0u64;
+    let mut rows_changed = 0u64;
+    let mut rows_unchanged = 0u64;
+
+    for key in all_keys {
+        let key_obj = key_object_raw(&options.key_columns, &key);
+        let in_a = indexed_a.get(&key);
+        let in_b = indexed_b.get(&key);
+
+        match (in_a, in_b) {
+            (None, Some((_, row_b))) => {
+                rows_added += 1;
+                events.push(json!({
+                    "type": "added",
+                    "key": key_obj,
+                    "row": row_to_value_raw(row_b)
+                }));
+            }
+            (Some((_, row_a)), None) => {
+                rows_removed += 1;
+                events.push(json!({
+                    "type": "removed",
+                    "key": key_obj,
+                    "row": row_to_value_raw(row_a)
+                }));
+            }
+            (Some((_, row_a)), Some((_, row_b))) => {
+                rows_total_compared += 1;
+
+                let changed_columns: Vec<String> = compare_columns
+                    .iter()
+                    .filter(|column| {
+                        row_a.get(*column).map(Vec::as_slice).unwrap_or_default()
+                            != row_b.get(*column).map(Vec::as_slice).unwrap_or_default()
+                    })
+                    .cloned()
+                    .collect();
+
+                if changed_columns.is_empty() {
+                    rows_unchanged += 1;
+                    if options.emit_unchanged {
+                        events.push(json!({
+                            "type": "unchanged",
+                            "key": key_obj,
+                            "row": row_to_value_raw(row_a)
+                        }));
+                    }
+                } else {
+                    rows_changed += 1;
+                    let delta = build_delta_raw(&changed_columns, row_a, row_b);
+
+                    events.push(json!({
+                        "type": "changed",
+                        "key": key_obj,
+                        "changed": changed_columns,
+                        "before": row_to_value_raw(row_a),
+                        "after": row_to_value_raw(row_b),
+                        "delta": Value::Object(delta)
+                    }));
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    events.push(json!({
+        "type": "stats",
+        "rows_total_compared": rows_total_compared,
+        "rows_added": rows_added,
+        "rows_removed": rows_removed,
+        "rows_changed": rows_changed,
+        "rows_unchanged": rows_unchanged
+    }));
+
+    Ok(events)
+}
+
+fn diff_rows_positional_raw(
+    a_header: Vec<String>,
+    a_rows: Vec<IndexedByteRow>,
+    b_header: Vec<String>,
+    b_rows: Vec<IndexedByteRow>,
+    options: &DiffOptions,
+) -> Result<Vec<Value>, DiffError> {
+    let compare_columns = comparison_columns(&a_header, &b_header, options.header_mode)?;
+
+    let mut events: Vec<Value> = Vec::new();
+    events.push(json!({
+        "type": "schema",
+        "columns_a": a_header,
+        "columns_b": b_header
+    }));
+
+    let mut rows_total_compared = 0u64;
+    let mut rows_added = 0u64;
+    let mut rows_removed = 0u64;
+    let mut rows_changed = 0u64;
+    let mut rows_unchanged = 0u64;
+
+    let total_rows = a_rows.len().max(b_rows.len());
+    for idx in 0..total_rows {
+        let row_index = idx + 2;
+        let in_a = a_rows.get(idx);
+        let in_b = b_rows.get(idx);
+
+        match (in_a, in_b) {
+            (None, Some((_, row_b))) => {
+                rows_added += 1;
+                events.push(json!({
+                    "type": "added",
+                    "row_index": row_index,
+                    "row": row_to_value_raw(row_b)
                 }));
             }
+            (Some((_, row_a)), None) => {
+                rows_removed += 1;
+                events.push(json!({
+                    "type": "removed",
+                    "row_index": row_index,
+                    "row": row_to_value_raw(row_a)
+                }));
+            }
+            (Some((_, row_a)), Some((_, row_b))) => {
+                rows_total_compared += 1;
+                let changed_columns: Vec<String> = compare_columns
+                    .iter()
+                    .filter(|column| {
+                        row_a.get(*column).map(Vec::as_slice).unwrap_or_default()
+                            != row_b.get(*column).map(Vec::as_slice).unwrap_or_default()
+                    })
+                    .cloned()
+                    .collect();
+
+                if changed_columns.is_empty() {
+                    rows_unchanged += 1;
+                    if options.emit_unchanged {
+                        events.push(json!({
+                            "type": "unchanged",
+                            "row_index": row_index,
+                            "row": row_to_value_raw(row_a)
+                        }));
+                    }
+                } else {
+                    rows_changed += 1;
+                    let delta = build_delta_raw(&changed_columns, row_a, row_b);
+                    events.push(json!({
+                        "type": "changed",
+                        "row_index": row_index,
+                        "changed": changed_columns,
+                        "before": row_to_value_raw(row_a),
+                        "after": row_to_value_raw(row_b),
+                        "delta": Value::Object(delta)
+                    }));
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    events.push(json!({
+        "type": "stats",
+        "rows_total_compared": rows_total_compared,
+        "rows_added": rows_added,
+        "rows_removed": rows_removed,
+        "rows_changed": rows_changed,
+        "rows_unchanged": rows_unchanged
+    }));
+
+    Ok(events)
+}
+
+/// Reconciliation-style join for `OutputMode::Join`: for every key selected
+/// by `options.join_selection`, emits one `joined` event carrying the key
+/// plus each compare column rendered as `{col}_a`/`{col}_b` (`null` when
+/// the row is absent on that side). Reuses `index_rows`, but never reports
+/// `added`/`removed`/`changed` — a row either appears in the join or it
+/// doesn't.
+fn diff_rows_joined(
+    a_header: Vec<String>,
+    a_rows: Vec<IndexedRow>,
+    b_header: Vec<String>,
+    b_rows: Vec<IndexedRow>,
+    options: &DiffOptions,
+) -> Result<Vec<Value>, DiffError> {
+    let compare_columns = comparison_columns(&a_header, &b_header, options.header_mode)?;
+
+    for key_column in &options.key_columns {
+        if !a_header.contains(key_column) || !b_header.contains(key_column) {
+            return Err(DiffError::new(
+                "missing_key_column",
+                format!("Missing key column: {key_column}"),
+            ));
+        }
+    }
+
+    let indexed_a = index_rows(a_rows, &options.key_columns, "A")?;
+    let indexed_b = index_rows(b_rows, &options.key_columns, "B")?;
+
+    let mut all_keys: Vec<Vec<String>> = indexed_a
+        .keys()
+        .chain(indexed_b.keys())
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    all_keys.sort();
+
+    let mut events: Vec<Value> = Vec::new();
+    events.push(json!({
+        "type": "schema",
+        "columns_a": a_header,
+        "columns_b": b_header
+    }));
+
+    let mut rows_matched = 0u64;
+    let mut rows_only_a = 0u64;
+    let mut rows_only_b = 0u64;
+
+    for key in all_keys {
+        let in_a = indexed_a.get(&key);
+        let in_b = indexed_b.get(&key);
+
+        let include = matches!(
+            (in_a.is_some(), in_b.is_some(), options.join_selection),
+            (true, true, _)
+                | (true, false, JoinSelection::Left | JoinSelection::Full)
+                | (false, true, JoinSelection::Right | JoinSelection::Full)
+        );
+        if !include {
+            continue;
+        }
+
+        match (in_a.is_some(), in_b.is_some()) {
+            (true, true) => rows_matched += 1,
+            (true, false) => rows_only_a += 1,
+            (false, true) => rows_only_b += 1,
+            (false, false) => {}
+        }
+
+        let key_obj = key_object(&options.key_columns, &key);
+        let mut row = Map::new();
+        for column in &compare_columns {
+            let value_a = in_a.and_then(|(_, row)| row.get(column).cloned());
+            let value_b = in_b.and_then(|(_, row)| row.get(column).cloned());
+            row.insert(
+                format!("{column}_a"),
+                value_a.map_or(Value::Null, Value::String),
+            );
+            row.insert(
+                format!("{column}_b"),
+                value_b.map_or(Value::Null, Value::String),
+            );
+        }
+
+        events.push(json!({
+            "type": "joined",
+            "key": key_obj,
+            "row": Value::Object(row)
+        }));
+    }
+
+    events.push(json!({
+        "type": "stats",
+        "rows_matched": rows_matched,
+        "rows_only_a": rows_only_a,
+        "rows_only_b": rows_only_b
+    }));
+
+    Ok(events)
+}
+
+pub fn diff_csv_files(
+    a_path: &Path,
+    b_path: &Path,
+    options: &DiffOptions,
+) -> Result<Vec<Value>, DiffError> {
+    if options.output_mode == OutputMode::Join {
+        if options.key_columns.is_empty() {
+            return Err(DiffError::new(
+                "missing_key_column",
+                "Join output mode requires key_columns",
+            ));
+        }
+        if options.lossless_bytes {
+            return Err(DiffError::new(
+                "unsupported_option_combination",
+                "Join output mode does not support lossless_bytes",
+            ));
+        }
+        let (a_header, a_rows) = read_csv(a_path, "A", options)?;
+        let (b_header, b_rows) = read_csv(b_path, "B", options)?;
+        return diff_rows_joined(a_header, a_rows, b_header, b_rows, options);
+    }
+
+    if options.lossless_bytes {
+        let (a_header, a_rows) = read_csv_raw(a_path, "A", options)?;
+        let (b_header, b_rows) = read_csv_raw(b_path, "B", options)?;
+        return if options.key_columns.is_empty() {
+            diff_rows_positional_raw(a_header, a_rows, b_header, b_rows, options)
+        } else {
+            diff_rows_keyed_raw(a_header, a_rows, b_header, b_rows, options)
+        };
+    }
+
+    let (a_header, a_rows) = read_csv(a_path, "A", options)?;
+    let (b_header, b_rows) = read_csv(b_path, "B", options)?;
+    if options.key_columns.is_empty() {
+        diff_rows_positional(a_header, a_rows, b_header, b_rows, options)
+    } else {
+        diff_rows_keyed(a_header, a_rows, b_header, b_rows, options)
+    }
+}
+
+pub fn diff_csv_bytes(
+    a_bytes: &[u8],
+    b_bytes: &[u8],
+    options: &DiffOptions,
+) -> Result<Vec<Value>, DiffError> {
+    if options.output_mode == OutputMode::Join {
+        if options.key_columns.is_empty() {
+            return Err(DiffError::new(
+                "missing_key_column",
+                "Join output mode requires key_columns",
+            ));
+        }
+        if options.lossless_bytes {
+            return Err(DiffError::new(
+                "unsupported_option_combination",
+                "Join output mode does not support lossless_bytes",
+            ));
+        }
+        let (a_header, a_rows) =
+            read_csv_reader(std::io::Cursor::new(a_bytes), "A", "<memory:a>", options)?;
+        let (b_header, b_rows) =
+            read_csv_reader(std::io::Cursor::new(b_bytes), "B", "<memory:b>", options)?;
+        return diff_rows_joined(a_header, a_rows, b_header, b_rows, options);
+    }
+
+    if options.lossless_bytes {
+        let (a_header, a_rows) =
+            read_csv_reader_raw(std::io::Cursor::new(a_bytes), "A", "<memory:a>", options)?;
+        let (b_header, b_rows) =
+            read_csv_reader_raw(std::io::Cursor::new(b_bytes), "B", "<memory:b>", options)?;
+        return if options.key_columns.is_empty() {
+            diff_rows_positional_raw(a_header, a_rows, b_header, b_rows, options)
+        } else {
+            diff_rows_keyed_raw(a_header, a_rows, b_header, b_rows, options)
+        };
+    }
+
+    let (a_header, a_rows) =
+        read_csv_reader(std::io::Cursor::new(a_bytes), "A", "<memory:a>", options)?;
+    let (b_header, b_rows) =
+        read_csv_reader(std::io::Cursor::new(b_bytes), "B", "<memory:b>", options)?;
+    if options.key_columns.is_empty() {
+        diff_rows_positional(a_header, a_rows, b_header, b_rows, options)
+    } else {
+        diff_rows_keyed(a_header, a_rows, b_header, b_rows, options)
+    }
+}
+
+/// Streaming counterpart to `diff_csv_files`: `schema`, per-row, and `stats`
+/// events are passed to `sink` as they're produced instead of being
+/// collected into a `Vec<Value>`, bounding memory to the size of the parsed
+/// rows rather than rows-plus-entire-event-list. The positional path
+/// streams the comparison directly; the keyed path still has to index both
+/// sides up front, but walks the sorted key set and sinks each event
+/// without collecting them first. `lossless_bytes` and `OutputMode::Join`
+/// are not handled here: rows are always read as UTF-8 strings and diffed.
+pub fn diff_csv_files_streaming<F>(
+    a_path: &Path,
+    b_path: &Path,
+    options: &DiffOptions,
+    mut sink: F,
+) -> Result<(), DiffError>
+where
+    F: FnMut(Value) -> Result<(), DiffError>,
+{
+    let (a_header, a_rows) = read_csv(a_path, "A", options)?;
+    let (b_header, b_rows) = read_csv(b_path, "B", options)?;
+    diff_rows_streaming(a_header, a_rows, b_header, b_rows, options, &mut sink)
+}
+
+/// Streaming counterpart to `diff_csv_bytes`. See `diff_csv_files_streaming`.
+pub fn diff_csv_bytes_streaming<F>(
+    a_bytes: &[u8],
+    b_bytes: &[u8],
+    options: &DiffOptions,
+    mut sink: F,
+) -> Result<(), DiffError>
+where
+    F: FnMut(Value) -> Result<(), DiffError>,
+{
+    let (a_header, a_rows) =
+        read_csv_reader(std::io::Cursor::new(a_bytes), "A", "<memory:a>", options)?;
+    let (b_header, b_rows) =
+        read_csv_reader(std::io::Cursor::new(b_bytes), "B", "<memory:b>", options)?;
+    diff_rows_streaming(a_header, a_rows, b_header, b_rows, options, &mut sink)
+}
+
+fn diff_rows_streaming(
+    a_header: Vec<String>,
+    a_rows: Vec<IndexedRow>,
+    b_header: Vec<String>,
+    b_rows: Vec<IndexedRow>,
+    options: &DiffOptions,
+    sink: &mut dyn FnMut(Value) -> Result<(), DiffError>,
+) -> Result<(), DiffError> {
+    if options.key_columns.is_empty() {
+        if options.ignore_row_order {
+            diff_rows_positional_unordered_streaming(
+                a_header, a_rows, b_header, b_rows, options, sink,
+            )
+        } else {
+            diff_rows_positional_streaming(a_header, a_rows, b_header, b_rows, options, sink)
+        }
+    } else {
+        diff_rows_keyed_streaming(a_header, a_rows, b_header, b_rows, options, sink)
+    }
+}
+
+fn diff_rows_keyed_streaming(
+    a_header: Vec<String>,
+    a_rows: Vec<IndexedRow>,
+    b_header: Vec<String>,
+    b_rows: Vec<IndexedRow>,
+    options: &DiffOptions,
+    sink: &mut dyn FnMut(Value) -> Result<(), DiffError>,
+) -> Result<(), DiffError> {
+    let compare_columns = comparison_columns(&a_header, &b_header, options.header_mode)?;
+
+    for key_column in &options.key_columns {
+        if !a_header.contains(key_column) || !b_header.contains(key_column) {
+            return Err(DiffError::new(
+                "missing_key_column",
+                format!("Missing key column: {key_column}"),
+            ));
+        }
+    }
+
+    let column_types = effective_column_types(&compare_columns, &a_rows, &b_rows, options);
+
+    let indexed_a = index_rows(a_rows, &options.key_columns, "A")?;
+    let indexed_b = index_rows(b_rows, &options.key_columns, "B")?;
+
+    let mut all_keys: Vec<Vec<String>> = indexed_a
+        .keys()
+        .chain(indexed_b.keys())
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    all_keys.sort();
+
+    sink(json!({
+        "type": "schema",
+        "columns_a": a_header,
+        "columns_b": b_header
+    }))?;
+
+    let mut rows_total_compared = 0u64;
+    let mut rows_added = 0u64;
+    let mut rows_removed = 0u64;
+    let mut rows_changed = 0u64;
+    let mut rows_unchanged = 0u64;
+
+    for key in all_keys {
+        let key_obj = key_object(&options.key_columns, &key);
+        let in_a = indexed_a.get(&key);
+        let in_b = indexed_b.get(&key);
+
+        match (in_a, in_b) {
+            (None, Some((_, row_b))) => {
+                rows_added += 1;
+                sink(json!({
+                    "type": "added",
+                    "key": key_obj,
+                    "row": row_to_value(row_b)
+                }))?;
+            }
+            (Some((_, row_a)), None) => {
+                rows_removed += 1;
+                sink(json!({
+                    "type": "removed",
+                    "key": key_obj,
+                    "row": row_to_value(row_a)
+                }))?;
+            }
             (Some((_, row_a)), Some((_, row_b))) => {
                 rows_total_compared += 1;
 
                 let changed_columns: Vec<String> = compare_columns
                     .iter()
-                    .filter(|column| row_a.get(*column) != row_b.get(*column))
+                    .filter(|column| {
+                        let value_a = row_a.get(*column).map(String::as_str).unwrap_or_default();
+                        let value_b = row_b.get(*column).map(String::as_str).unwrap_or_default();
+                        !values_equal(
+                            column,
+                            value_a,
+                            value_b,
+                            column_types.get(*column).copied(),
+                            options,
+                        )
+                    })
                     .cloned()
                     .collect();
 
                 if changed_columns.is_empty() {
                     rows_unchanged += 1;
                     if options.emit_unchanged {
-                        events.push(json!({
+                        sink(json!({
                             "type": "unchanged",
                             "key": key_obj,
                             "row": row_to_value(row_a)
-                        }));
+                        }))?;
                     }
                 } else {
                     rows_changed += 1;
-                    let mut delta = Map::new();
-                    for column in &changed_columns {
-                        delta.insert(
-                            column.clone(),
-                            json!({
-                                "from": row_a.get(column).cloned().unwrap_or_default(),
-                                "to": row_b.get(column).cloned().unwrap_or_default()
-                            }),
-                        );
-                    }
-
-                    events.push(json!({
+                    let delta = build_delta(&changed_columns, row_a, row_b, options);
+                    let (before, after) = if options.drop_equal_fields {
+                        (
+                            row_to_value_changed_only(
+                                row_a,
+                                &changed_columns,
+                                &options.key_columns,
+                            ),
+                            row_to_value_changed_only(
+                                row_b,
+                                &changed_columns,
+                                &options.key_columns,
+                            ),
+                        )
+                    } else {
+                        (row_to_value(row_a), row_to_value(row_b))
+                    };
+
+                    sink(json!({
                         "type": "changed",
                         "key": key_obj,
                         "changed": changed_columns,
-                        "before": row_to_value(row_a),
-                        "after": row_to_value(row_b),
+                        "before": before,
+                        "after": after,
                         "delta": Value::Object(delta)
-                    }));
+                    }))?;
                 }
             }
             (None, None) => {}
         }
     }
 
-    events.push(json!({
+    sink(json!({
         "type": "stats",
         "rows_total_compared": rows_total_compared,
         "rows_added": rows_added,
         "rows_removed": rows_removed,
         "rows_changed": rows_changed,
         "rows_unchanged": rows_unchanged
-    }));
+    }))?;
 
-    Ok(events)
+    Ok(())
 }
 
-fn diff_rows_positional(
+fn diff_rows_positional_streaming(
     a_header: Vec<String>,
     a_rows: Vec<IndexedRow>,
     b_header: Vec<String>,
     b_rows: Vec<IndexedRow>,
     options: &DiffOptions,
-) -> Result<Vec<Value>, DiffError> {
+    sink: &mut dyn FnMut(Value) -> Result<(), DiffError>,
+) -> Result<(), DiffError> {
     let compare_columns = comparison_columns(&a_header, &b_header, options.header_mode)?;
+    let column_types = effective_column_types(&compare_columns, &a_rows, &b_rows, options);
 
-    let mut events: Vec<Value> = Vec::new();
-    events.push(json!({
+    sink(json!({
         "type": "schema",
         "columns_a": a_header,
         "columns_b": b_header
-    }));
+    }))?;
 
     let mut rows_total_compared = 0u64;
     let mut rows_added = 0u64;
@@ -402,101 +2141,196 @@ fn diff_rows_positional(
         match (in_a, in_b) {
             (None, Some((_, row_b))) => {
                 rows_added += 1;
-                events.push(json!({
+                sink(json!({
                     "type": "added",
                     "row_index": row_index,
                     "row": row_to_value(row_b)
-                }));
+                }))?;
             }
             (Some((_, row_a)), None) => {
                 rows_removed += 1;
-                events.push(json!({
+                sink(json!({
                     "type": "removed",
                     "row_index": row_index,
                     "row": row_to_value(row_a)
-                }));
+                }))?;
             }
             (Some((_, row_a)), Some((_, row_b))) => {
                 rows_total_compared += 1;
                 let changed_columns: Vec<String> = compare_columns
                     .iter()
-                    .filter(|column| row_a.get(*column) != row_b.get(*column))
+                    .filter(|column| {
+                        let value_a = row_a.get(*column).map(String::as_str).unwrap_or_default();
+                        let value_b = row_b.get(*column).map(String::as_str).unwrap_or_default();
+                        !values_equal(
+                            column,
+                            value_a,
+                            value_b,
+                            column_types.get(*column).copied(),
+                            options,
+                        )
+                    })
                     .cloned()
                     .collect();
 
                 if changed_columns.is_empty() {
                     rows_unchanged += 1;
                     if options.emit_unchanged {
-                        events.push(json!({
+                        sink(json!({
                             "type": "unchanged",
                             "row_index": row_index,
                             "row": row_to_value(row_a)
-                        }));
+                        }))?;
                     }
                 } else {
                     rows_changed += 1;
-                    let mut delta = Map::new();
-                    for column in &changed_columns {
-                        delta.insert(
-                            column.clone(),
-                            json!({
-                                "from": row_a.get(column).cloned().unwrap_or_default(),
-                                "to": row_b.get(column).cloned().unwrap_or_default()
-                            }),
-                        );
-                    }
-                    events.push(json!({
+                    let delta = build_delta(&changed_columns, row_a, row_b, options);
+                    let (before, after) = if options.drop_equal_fields {
+                        (
+                            row_to_value_changed_only(
+                                row_a,
+                                &changed_columns,
+                                &options.key_columns,
+                            ),
+                            row_to_value_changed_only(
+                                row_b,
+                                &changed_columns,
+                                &options.key_columns,
+                            ),
+                        )
+                    } else {
+                        (row_to_value(row_a), row_to_value(row_b))
+                    };
+                    sink(json!({
                         "type": "changed",
                         "row_index": row_index,
                         "changed": changed_columns,
-                        "before": row_to_value(row_a),
-                        "after": row_to_value(row_b),
+                        "before": before,
+                        "after": after,
                         "delta": Value::Object(delta)
-                    }));
+                    }))?;
                 }
             }
             (None, None) => {}
         }
     }
 
-    events.push(json!({
+    sink(json!({
         "type": "stats",
         "rows_total_compared": rows_total_compared,
         "rows_added": rows_added,
         "rows_removed": rows_removed,
         "rows_changed": rows_changed,
         "rows_unchanged": rows_unchanged
-    }));
+    }))?;
 
-    Ok(events)
+    Ok(())
 }
 
-pub fn diff_csv_files(
-    a_path: &Path,
-    b_path: &Path,
+fn diff_rows_positional_unordered_streaming(
+    a_header: Vec<String>,
+    a_rows: Vec<IndexedRow>,
+    b_header: Vec<String>,
+    b_rows: Vec<IndexedRow>,
     options: &DiffOptions,
-) -> Result<Vec<Value>, DiffError> {
-    let (a_header, a_rows) = read_csv(a_path, "A")?;
-    let (b_header, b_rows) = read_csv(b_path, "B")?;
-    if options.key_columns.is_empty() {
-        diff_rows_positional(a_header, a_rows, b_header, b_rows, options)
-    } else {
-        diff_rows_keyed(a_header, a_rows, b_header, b_rows, options)
-    }
-}
+    sink: &mut dyn FnMut(Value) -> Result<(), DiffError>,
+) -> Result<(), DiffError> {
+    let compare_columns = comparison_columns(&a_header, &b_header, options.header_mode)?;
 
-pub fn diff_csv_bytes(
-    a_bytes: &[u8],
-    b_bytes: &[u8],
-    options: &DiffOptions,
-) -> Result<Vec<Value>, DiffError> {
-    let (a_header, a_rows) = read_csv_reader(std::io::Cursor::new(a_bytes), "A", "<memory:a>")?;
-    let (b_header, b_rows) = read_csv_reader(std::io::Cursor::new(b_bytes), "B", "<memory:b>")?;
-    if options.key_columns.is_empty() {
-        diff_rows_positional(a_header, a_rows, b_header, b_rows, options)
-    } else {
-        diff_rows_keyed(a_header, a_rows, b_header, b_rows, options)
+    sink(json!({
+        "type": "schema",
+        "columns_a": a_header,
+        "columns_b": b_header
+    }))?;
+
+    let multiset_a = build_row_multiset(&a_rows, &compare_columns);
+    let multiset_b = build_row_multiset(&b_rows, &compare_columns);
+
+    let mut all_hashes: Vec<u64> = multiset_a
+        .keys()
+        .chain(multiset_b.keys())
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    all_hashes.sort_unstable();
+
+    let empty: Vec<(String, usize)> = Vec::new();
+    let mut rows_added = 0u64;
+    let mut rows_removed = 0u64;
+    let mut rows_unchanged = 0u64;
+
+    for hash in all_hashes {
+        let entries_a = multiset_a.get(&hash).unwrap_or(&empty);
+        let entries_b = multiset_b.get(&hash).unwrap_or(&empty);
+
+        let mut canonicals: Vec<&String> = entries_a
+            .iter()
+            .chain(entries_b.iter())
+            .map(|(canonical, _)| canonical)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        canonicals.sort();
+
+        for canonical in canonicals {
+            let mut positions_a: Vec<usize> = entries_a
+                .iter()
+                .filter(|(entry_canonical, _)| entry_canonical == canonical)
+                .map(|(_, position)| *position)
+                .collect();
+            let mut positions_b: Vec<usize> = entries_b
+                .iter()
+                .filter(|(entry_canonical, _)| entry_canonical == canonical)
+                .map(|(_, position)| *position)
+                .collect();
+            positions_a.sort_unstable();
+            positions_b.sort_unstable();
+
+            let matched = positions_a.len().min(positions_b.len());
+            rows_unchanged += matched as u64;
+            if options.emit_unchanged {
+                for &position in &positions_a[..matched] {
+                    let (row_index, row) = &a_rows[position];
+                    sink(json!({
+                        "type": "unchanged",
+                        "row_index": row_index,
+                        "row": row_to_value(row)
+                    }))?;
+                }
+            }
+
+            for &position in &positions_b[matched..] {
+                rows_added += 1;
+                let (row_index, row) = &b_rows[position];
+                sink(json!({
+                    "type": "added",
+                    "row_index": row_index,
+                    "row": row_to_value(row)
+                }))?;
+            }
+            for &position in &positions_a[matched..] {
+                rows_removed += 1;
+                let (row_index, row) = &a_rows[position];
+                sink(json!({
+                    "type": "removed",
+                    "row_index": row_index,
+                    "row": row_to_value(row)
+                }))?;
+            }
+        }
     }
+
+    sink(json!({
+        "type": "stats",
+        "rows_total_compared": rows_unchanged,
+        "rows_added": rows_added,
+        "rows_removed": rows_removed,
+        "rows_changed": 0,
+        "rows_unchanged": rows_unchanged
+    }))?;
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -514,7 +2348,13 @@ mod tests {
         std::env::temp_dir().join(format!("diffly-{name}-{}-{nanos}.csv", std::process::id()))
     }
 
-    fn write_csv(name: &str, content: &str) -> PathBuf {
+    fn write_csv(name: &str, content: &str) -> PathBuf {
+        let path = temp_csv_path(name);
+        fs::write(&path, content).expect("failed to write csv fixture");
+        path
+    }
+
+    fn write_csv_bytes(name: &str, content: &[u8]) -> PathBuf {
         let path = temp_csv_path(name);
         fs::write(&path, content).expect("failed to write csv fixture");
         path
@@ -525,6 +2365,7 @@ mod tests {
             key_columns: vec!["id".to_string()],
             header_mode: HeaderMode::Strict,
             emit_unchanged: false,
+            ..DiffOptions::default()
         }
     }
 
@@ -533,6 +2374,7 @@ mod tests {
             key_columns: Vec::new(),
             header_mode: HeaderMode::Strict,
             emit_unchanged: false,
+            ..DiffOptions::default()
         }
     }
 
@@ -638,4 +2480,589 @@ mod tests {
         let _ = fs::remove_file(a);
         let _ = fs::remove_file(b);
     }
+
+    #[test]
+    fn tab_delimited_dialect_diffs_correctly() {
+        let options = DiffOptions {
+            delimiter: b'\t',
+            ..default_options()
+        };
+        let a = write_csv("dialect-tsv-a", "id\tname\n1\tAlice\n2\tBob\n");
+        let b = write_csv("dialect-tsv-b", "id\tname\n1\tAlicia\n3\tCara\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let types: Vec<&str> = events
+            .iter()
+            .filter_map(|event| event.get("type").and_then(Value::as_str))
+            .collect();
+        assert!(types.contains(&"changed"));
+        assert!(types.contains(&"added"));
+        assert!(types.contains(&"removed"));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn trim_all_strips_whitespace_from_headers_and_fields() {
+        let options = DiffOptions {
+            trim: CsvTrim::All,
+            ..default_options()
+        };
+        let a = write_csv("dialect-trim-a", " id , name \n 1 , Alice \n");
+        let b = write_csv("dialect-trim-b", "id,name\n1,Alice\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let stats = events.last().expect("stats should be present");
+        assert_eq!(stats.get("rows_changed").and_then(Value::as_u64), Some(0));
+        assert_eq!(
+            stats.get("rows_total_compared").and_then(Value::as_u64),
+            Some(1)
+        );
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn csv_trim_parse_rejects_unknown_mode() {
+        assert_eq!(CsvTrim::parse("all"), Ok(CsvTrim::All));
+        let err = CsvTrim::parse("everything").expect_err("unknown mode should fail");
+        assert_eq!(err.code, "invalid_trim_mode");
+    }
+
+    #[test]
+    fn flexible_mode_pads_short_rows_and_ignores_extra_fields() {
+        let options = DiffOptions {
+            flexible: true,
+            ..default_options()
+        };
+        let a = write_csv(
+            "dialect-flexible-a",
+            "id,name,region\n1,Alice\n2,Bob,eu,extra\n",
+        );
+        let b = write_csv("dialect-flexible-b", "id,name,region\n1,Alice,\n2,Bob,eu\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let stats = events.last().expect("stats should be present");
+        assert_eq!(stats.get("rows_changed").and_then(Value::as_u64), Some(0));
+        assert_eq!(
+            stats.get("rows_total_compared").and_then(Value::as_u64),
+            Some(2)
+        );
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn non_flexible_mode_still_rejects_row_width_mismatch() {
+        let a = write_csv("dialect-strict-a", "id,name\n1,Alice\n2,Bob,extra\n");
+        let b = write_csv("dialect-strict-b", "id,name\n1,Alice\n2,Bob\n");
+
+        let err =
+            diff_csv_files(&a, &b, &default_options()).expect_err("width mismatch should fail");
+        assert_eq!(err.code, "row_width_mismatch");
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn float_column_within_tolerance_is_unchanged() {
+        let mut options = default_options();
+        options
+            .column_types
+            .insert("amount".to_string(), ColumnType::Float);
+        options.float_tolerance.insert(
+            "amount".to_string(),
+            FloatTolerance {
+                absolute: 0.01,
+                relative: 0.0,
+            },
+        );
+        let a = write_csv("type-float-a", "id,amount\n1,1.0\n");
+        let b = write_csv("type-float-b", "id,amount\n1,1.00\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let stats = events.last().expect("stats should be present");
+        assert_eq!(stats.get("rows_changed").and_then(Value::as_u64), Some(0));
+        assert_eq!(stats.get("rows_unchanged").and_then(Value::as_u64), Some(1));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn float_column_outside_tolerance_is_changed() {
+        let mut options = default_options();
+        options
+            .column_types
+            .insert("amount".to_string(), ColumnType::Float);
+        let a = write_csv("type-float-outside-a", "id,amount\n1,1.0\n");
+        let b = write_csv("type-float-outside-b", "id,amount\n1,1.5\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let stats = events.last().expect("stats should be present");
+        assert_eq!(stats.get("rows_changed").and_then(Value::as_u64), Some(1));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn timestamp_column_compares_at_configured_granularity() {
+        let mut options = default_options();
+        options
+            .column_types
+            .insert("seen_at".to_string(), ColumnType::Timestamp);
+        options
+            .timestamp_granularity
+            .insert("seen_at".to_string(), TimestampGranularity::Second);
+        let a = write_csv(
+            "type-timestamp-a",
+            "id,seen_at\n1,2018-12-13T12:12:10.011Z\n",
+        );
+        let b = write_csv(
+            "type-timestamp-b",
+            "id,seen_at\n1,2018-12-13T12:12:10.900+00:00\n",
+        );
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let stats = events.last().expect("stats should be present");
+        assert_eq!(stats.get("rows_changed").and_then(Value::as_u64), Some(0));
+        assert_eq!(stats.get("rows_unchanged").and_then(Value::as_u64), Some(1));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn bool_column_case_folds() {
+        let mut options = default_options();
+        options
+            .column_types
+            .insert("active".to_string(), ColumnType::Bool);
+        let a = write_csv("type-bool-a", "id,active\n1,TRUE\n");
+        let b = write_csv("type-bool-b", "id,active\n1,true\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let stats = events.last().expect("stats should be present");
+        assert_eq!(stats.get("rows_changed").and_then(Value::as_u64), Some(0));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn inferred_column_types_apply_without_explicit_declaration() {
+        let mut options = default_options();
+        options.infer_column_types = true;
+        let a = write_csv("type-infer-a", "id,amount\n1,1.0\n2,2\n");
+        let b = write_csv("type-infer-b", "id,amount\n1,1.00\n2,2\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let stats = events.last().expect("stats should be present");
+        assert_eq!(stats.get("rows_changed").and_then(Value::as_u64), Some(0));
+        assert_eq!(stats.get("rows_unchanged").and_then(Value::as_u64), Some(2));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn field_diff_segments_word_mode_highlights_the_changed_word() {
+        let segments =
+            field_diff_segments(FieldDiffMode::Word, "the quick fox", "the slow fox", 4096)
+                .expect("segments should be computed");
+
+        assert_eq!(
+            segments,
+            vec![
+                json!({"op": "equal", "text": "the "}),
+                json!({"op": "delete", "text": "quick"}),
+                json!({"op": "insert", "text": "slow"}),
+                json!({"op": "equal", "text": " fox"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn field_diff_segments_skips_values_over_the_length_cap() {
+        let long_value = "x".repeat(10);
+        assert!(field_diff_segments(FieldDiffMode::Char, &long_value, "y", 5).is_none());
+    }
+
+    #[test]
+    fn field_diff_segments_none_mode_is_disabled() {
+        assert!(field_diff_segments(FieldDiffMode::None, "a", "b", 4096).is_none());
+    }
+
+    #[test]
+    fn changed_event_carries_segments_when_field_diff_is_enabled() {
+        let mut options = default_options();
+        options.field_diff = FieldDiffMode::Word;
+        let a = write_csv("field-diff-a", "id,note\n1,the quick fox\n");
+        let b = write_csv("field-diff-b", "id,note\n1,the slow fox\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let changed = events
+            .iter()
+            .find(|event| event.get("type").and_then(Value::as_str) == Some("changed"))
+            .expect("changed event should be present");
+        let segments = changed["delta"]["note"]["segments"]
+            .as_array()
+            .expect("segments array");
+        assert_eq!(segments.len(), 4);
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn changed_event_has_no_segments_when_field_diff_is_disabled() {
+        let options = default_options();
+        let a = write_csv("field-diff-off-a", "id,note\n1,the quick fox\n");
+        let b = write_csv("field-diff-off-b", "id,note\n1,the slow fox\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let changed = events
+            .iter()
+            .find(|event| event.get("type").and_then(Value::as_str) == Some("changed"))
+            .expect("changed event should be present");
+        assert!(changed["delta"]["note"].get("segments").is_none());
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn lossless_bytes_base64_encodes_non_utf8_cells() {
+        let mut options = default_options();
+        options.lossless_bytes = true;
+        let a = write_csv("lossless-a", "id,name\n1,Alice\n");
+        let mut b_content = b"id,name\n1,".to_vec();
+        b_content.extend_from_slice(&[0xff, 0xfe]);
+        b_content.push(b'\n');
+        let b = write_csv_bytes("lossless-b", &b_content);
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let changed = events
+            .iter()
+            .find(|event| event.get("type").and_then(Value::as_str) == Some("changed"))
+            .expect("changed event should be present");
+        let to = changed["delta"]["name"]["to"]
+            .as_str()
+            .expect("to should be a string");
+        assert!(to.starts_with("base64:"));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn lossless_bytes_still_parses_plain_utf8_csv() {
+        let mut options = positional_options();
+        options.lossless_bytes = true;
+        let a = write_csv("lossless-plain-a", "id,name\n1,Alice\n");
+        let b = write_csv("lossless-plain-b", "id,name\n1,Alicia\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let changed = events
+            .iter()
+            .find(|event| event.get("type").and_then(Value::as_str) == Some("changed"))
+            .expect("changed event should be present");
+        assert_eq!(changed["delta"]["name"]["to"], json!("Alicia"));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn keyed_diff_with_explicit_jobs_matches_auto_jobs() {
+        let mut jobs_options = default_options();
+        jobs_options.jobs = 2;
+        let a = write_csv(
+            "jobs-a",
+            "id,name\n1,Alice\n2,Bob\n3,Carol\n4,Dave\n5,Eve\n",
+        );
+        let b = write_csv(
+            "jobs-b",
+            "id,name\n1,Alice\n2,Bobby\n4,Dave\n5,Evelyn\n6,Frank\n",
+        );
+
+        let auto_events = diff_csv_files(&a, &b, &default_options()).expect("diff should succeed");
+        let jobs_events = diff_csv_files(&a, &b, &jobs_options).expect("diff should succeed");
+        assert_eq!(auto_events, jobs_events);
+
+        let stats = jobs_events
+            .iter()
+            .find(|event| event.get("type").and_then(Value::as_str) == Some("stats"))
+            .expect("stats event should be present");
+        assert_eq!(stats["rows_added"], json!(1));
+        assert_eq!(stats["rows_removed"], json!(1));
+        assert_eq!(stats["rows_changed"], json!(2));
+        assert_eq!(stats["rows_unchanged"], json!(2));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn drop_equal_fields_keeps_only_changed_and_key_columns() {
+        let mut options = default_options();
+        options.drop_equal_fields = true;
+        let a = write_csv("drop-equal-a", "id,name,city\n1,Alice,Paris\n");
+        let b = write_csv("drop-equal-b", "id,name,city\n1,Alice,London\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let changed = events
+            .iter()
+            .find(|event| event.get("type").and_then(Value::as_str) == Some("changed"))
+            .expect("changed event should be present");
+
+        assert_eq!(changed["before"], json!({"id": "1", "city": "Paris"}));
+        assert_eq!(changed["after"], json!({"id": "1", "city": "London"}));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn ignore_row_order_matches_reordered_rows_as_unchanged() {
+        let mut options = positional_options();
+        options.ignore_row_order = true;
+        let a = write_csv("unordered-a", "id,name\n1,Alice\n2,Bob\n");
+        let b = write_csv("unordered-b", "id,name\n2,Bob\n1,Alice\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        assert!(events
+            .iter()
+            .all(|event| event.get("type").and_then(Value::as_str) != Some("changed")));
+
+        let stats = events
+            .iter()
+            .find(|event| event.get("type").and_then(Value::as_str) == Some("stats"))
+            .expect("stats event should be present");
+        assert_eq!(stats["rows_added"], json!(0));
+        assert_eq!(stats["rows_removed"], json!(0));
+        assert_eq!(stats["rows_unchanged"], json!(2));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn ignore_row_order_preserves_duplicate_multiplicity() {
+        let mut options = positional_options();
+        options.ignore_row_order = true;
+        let a = write_csv("unordered-dup-a", "id,name\n1,Alice\n1,Alice\n1,Alice\n");
+        let b = write_csv("unordered-dup-b", "id,name\n1,Alice\n1,Alice\n");
+
+        let events = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+        let stats = events
+            .iter()
+            .find(|event| event.get("type").and_then(Value::as_str) == Some("stats"))
+            .expect("stats event should be present");
+        assert_eq!(stats["rows_unchanged"], json!(2));
+        assert_eq!(stats["rows_removed"], json!(1));
+        assert_eq!(stats["rows_added"], json!(0));
+
+        let removed = events
+            .iter()
+            .filter(|event| event.get("type").and_then(Value::as_str) == Some("removed"))
+            .count();
+        assert_eq!(removed, 1);
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn streaming_keyed_diff_matches_buffered_diff() {
+        let options = default_options();
+        let a = write_csv("stream-keyed-a", "id,name\n1,Alice\n2,Bob\n3,Carol\n");
+        let b = write_csv("stream-keyed-b", "id,name\n1,Alice\n2,Bobby\n4,Dave\n");
+
+        let buffered = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+
+        let mut streamed = Vec::new();
+        diff_csv_files_streaming(&a, &b, &options, |event| {
+            streamed.push(event);
+            Ok(())
+        })
+        .expect("streaming diff should succeed");
+
+        assert_eq!(buffered, streamed);
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn streaming_positional_diff_matches_buffered_diff() {
+        let options = positional_options();
+        let a = write_csv("stream-pos-a", "id,name\n1,Alice\n2,Bob\n");
+        let b = write_csv("stream-pos-b", "id,name\n1,Alice\n2,Bobby\n3,Carol\n");
+
+        let buffered = diff_csv_files(&a, &b, &options).expect("diff should succeed");
+
+        let mut streamed = Vec::new();
+        diff_csv_files_streaming(&a, &b, &options, |event| {
+            streamed.push(event);
+            Ok(())
+        })
+        .expect("streaming diff should succeed");
+
+        assert_eq!(buffered, streamed);
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn streaming_sink_error_aborts_the_walk() {
+        let options = default_options();
+        let a = write_csv("stream-err-a", "id,name\n1,Alice\n2,Bob\n");
+        let b = write_csv("stream-err-b", "id,name\n1,Alicia\n2,Bobby\n");
+
+        let mut seen = 0;
+        let err = diff_csv_files_streaming(&a, &b, &options, |_event| {
+            seen += 1;
+            Err(DiffError::new("sink_error", "sink failed"))
+        })
+        .expect_err("sink error should propagate");
+
+        assert_eq!(err.code, "sink_error");
+        assert_eq!(seen, 1);
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    fn join_options(join_selection: JoinSelection) -> DiffOptions {
+        DiffOptions {
+            key_columns: vec!["id".to_string()],
+            header_mode: HeaderMode::Strict,
+            output_mode: OutputMode::Join,
+            join_selection,
+            ..DiffOptions::default()
+        }
+    }
+
+    #[test]
+    fn inner_join_keeps_only_matched_keys() {
+        let a = write_csv("join-inner-a", "id,name\n1,Alice\n2,Bob\n");
+        let b = write_csv("join-inner-b", "id,name\n2,Bobby\n3,Cara\n");
+
+        let events = diff_csv_files(&a, &b, &join_options(JoinSelection::Inner))
+            .expect("inner join should succeed");
+
+        let joined: Vec<&Value> = events.iter().filter(|e| e["type"] == "joined").collect();
+        assert_eq!(joined.len(), 1);
+        assert_eq!(joined[0]["key"]["id"], "2");
+        assert_eq!(joined[0]["row"]["name_a"], "Bob");
+        assert_eq!(joined[0]["row"]["name_b"], "Bobby");
+
+        let stats = events.iter().find(|e| e["type"] == "stats").unwrap();
+        assert_eq!(stats["rows_matched"], 1);
+        assert_eq!(stats["rows_only_a"], 0);
+        assert_eq!(stats["rows_only_b"], 0);
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn left_join_keeps_matched_and_a_only_rows() {
+        let a = write_csv("join-left-a", "id,name\n1,Alice\n2,Bob\n");
+        let b = write_csv("join-left-b", "id,name\n2,Bobby\n3,Cara\n");
+
+        let events = diff_csv_files(&a, &b, &join_options(JoinSelection::Left))
+            .expect("left join should succeed");
+
+        let joined: Vec<&Value> = events.iter().filter(|e| e["type"] == "joined").collect();
+        assert_eq!(joined.len(), 2);
+        assert_eq!(joined[0]["key"]["id"], "1");
+        assert_eq!(joined[0]["row"]["name_a"], "Alice");
+        assert_eq!(joined[0]["row"]["name_b"], Value::Null);
+        assert_eq!(joined[1]["key"]["id"], "2");
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn right_join_keeps_matched_and_b_only_rows() {
+        let a = write_csv("join-right-a", "id,name\n1,Alice\n2,Bob\n");
+        let b = write_csv("join-right-b", "id,name\n2,Bobby\n3,Cara\n");
+
+        let events = diff_csv_files(&a, &b, &join_options(JoinSelection::Right))
+            .expect("right join should succeed");
+
+        let joined: Vec<&Value> = events.iter().filter(|e| e["type"] == "joined").collect();
+        assert_eq!(joined.len(), 2);
+        assert_eq!(joined[0]["key"]["id"], "2");
+        assert_eq!(joined[1]["key"]["id"], "3");
+        assert_eq!(joined[1]["row"]["name_a"], Value::Null);
+        assert_eq!(joined[1]["row"]["name_b"], "Cara");
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn full_join_keeps_every_key_from_both_sides() {
+        let a = write_csv("join-full-a", "id,name\n1,Alice\n2,Bob\n");
+        let b = write_csv("join-full-b", "id,name\n2,Bobby\n3,Cara\n");
+
+        let events = diff_csv_files(&a, &b, &join_options(JoinSelection::Full))
+            .expect("full join should succeed");
+
+        let joined: Vec<&Value> = events.iter().filter(|e| e["type"] == "joined").collect();
+        assert_eq!(joined.len(), 3);
+
+        let stats = events.iter().find(|e| e["type"] == "stats").unwrap();
+        assert_eq!(stats["rows_matched"], 1);
+        assert_eq!(stats["rows_only_a"], 1);
+        assert_eq!(stats["rows_only_b"], 1);
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn join_requires_key_columns() {
+        let a = write_csv("join-no-key-a", "id,name\n1,Alice\n");
+        let b = write_csv("join-no-key-b", "id,name\n1,Alice\n");
+
+        let options = DiffOptions {
+            output_mode: OutputMode::Join,
+            ..DiffOptions::default()
+        };
+
+        let err = diff_csv_files(&a, &b, &options).expect_err("join without keys should fail");
+        assert_eq!(err.code, "missing_key_column");
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn join_rejects_lossless_bytes() {
+        let a = write_csv("join-bytes-a", "id,name\n1,Alice\n");
+        let b = write_csv("join-bytes-b", "id,name\n1,Alice\n");
+
+        let options = DiffOptions {
+            lossless_bytes: true,
+            ..join_options(JoinSelection::Full)
+        };
+
+        let err =
+            diff_csv_files(&a, &b, &options).expect_err("join with lossless_bytes should fail");
+        assert_eq!(err.code, "unsupported_option_combination");
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
 }
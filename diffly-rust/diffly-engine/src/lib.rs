@@ -1,11 +1,16 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::fs::{self, OpenOptions};
+#[cfg(feature = "gzip-spill")]
+use std::io::Read;
 use std::io::Write;
 use std::path::Path;
 
 use csv::{Reader, ReaderBuilder};
-use diffly_core::{diff_csv_files, DiffError, DiffOptions, HeaderMode};
+use diffly_core::{
+    diff_csv_files, diff_csv_files_streaming, field_diff_segments, values_equal, DiffError,
+    DiffOptions, HeaderMode,
+};
 use serde_json::{json, Map, Value};
 use tempfile::TempDir;
 
@@ -13,6 +18,29 @@ pub trait EventSink {
     fn on_event(&mut self, event: &Value) -> Result<(), String>;
 }
 
+/// Forwards every event to each wrapped sink in order, so a single pass
+/// over the diff can drive several independent output shapes at once.
+/// Stops at the first sink that errors, leaving later sinks un-notified
+/// of that event.
+pub struct TeeSink {
+    sinks: Vec<Box<dyn EventSink>>,
+}
+
+impl TeeSink {
+    pub fn new(sinks: Vec<Box<dyn EventSink>>) -> Self {
+        Self { sinks }
+    }
+}
+
+impl EventSink for TeeSink {
+    fn on_event(&mut self, event: &Value) -> Result<(), String> {
+        for sink in &mut self.sinks {
+            sink.on_event(event)?;
+        }
+        Ok(())
+    }
+}
+
 pub trait CancelCheck {
     fn cancelled(&self) -> bool;
 }
@@ -50,6 +78,44 @@ impl CancelCheck for NeverCancel {
 pub struct EngineRunConfig {
     pub emit_progress: bool,
     pub progress_interval_events: usize,
+    pub spill_policy: SpillPolicy,
+    /// Maximum records a single partition's side may hold before
+    /// `diff_partitioned_from_manifest_with_budget` recursively re-partitions
+    /// it. `usize::MAX` disables the recursive fallback entirely.
+    pub partition_memory_budget: usize,
+    /// When set, `run_keyed_to_sink_with_config` spills both inputs into this
+    /// many hash partitions and diffs them via
+    /// `diff_partitioned_from_manifest_with_budget` instead of the
+    /// in-memory `diff_csv_files` path. Only takes effect for keyed diffs,
+    /// since partitioning requires a key to hash on. `None` keeps the
+    /// existing unpartitioned behavior.
+    pub partition_count: Option<usize>,
+    /// Which partitioned diff implementation to use once `partition_count`
+    /// is set. Ignored when `partition_count` is `None`.
+    pub merge_strategy: MergeStrategy,
+    /// When set (and `partition_count` is `None`), `run_keyed_to_sink_with_config`
+    /// drives `diff_csv_files_streaming` instead of `diff_csv_files`, so
+    /// events reach `sink` as they're produced instead of being collected
+    /// into a `Vec<Value>` first. Progress events aren't emitted in this
+    /// mode since the total event count isn't known up front. Ignored once
+    /// `partition_count` is set, since the partitioned paths already stream
+    /// one partition at a time.
+    pub streaming: bool,
+}
+
+/// Selects between the two partitioned diff implementations once
+/// `EngineRunConfig::partition_count` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// `diff_partitioned_from_manifest_with_budget`: hashes each partition's
+    /// rows into memory, recursively re-partitioning any side that exceeds
+    /// `partition_memory_budget`.
+    Hashed,
+    /// `diff_partitioned_from_manifest_sorted`: externally sorts each
+    /// partition's two sides and merge-joins them, holding at most
+    /// `EXTERNAL_SORT_CHUNK_ROWS` parsed records per sort chunk; each
+    /// partition's spilled text is still read into memory whole.
+    Sorted,
 }
 
 impl Default for EngineRunConfig {
@@ -57,6 +123,33 @@ impl Default for EngineRunConfig {
         Self {
             emit_progress: false,
             progress_interval_events: 1000,
+            spill_policy: SpillPolicy::default(),
+            partition_memory_budget: usize::MAX,
+            partition_count: None,
+            merge_strategy: MergeStrategy::Hashed,
+            streaming: false,
+        }
+    }
+}
+
+/// Governs how aggressively `TempDirSpill` keeps partitions in memory before
+/// writing them to disk.
+#[derive(Debug, Clone, Copy)]
+pub struct SpillPolicy {
+    /// Bytes a single partition's in-memory buffer may grow to before it is
+    /// flushed to disk.
+    pub spill_bytes_limit: usize,
+    /// Fraction of total disk space on the temp-dir volume that must remain
+    /// free; `TempDirSpill::new` fails fast if creating the spill directory
+    /// would violate this reserve.
+    pub reserved_disk_ratio: f64,
+}
+
+impl Default for SpillPolicy {
+    fn default() -> Self {
+        Self {
+            spill_bytes_limit: 8 * 1024 * 1024,
+            reserved_disk_ratio: 0.05,
         }
     }
 }
@@ -85,21 +178,111 @@ pub fn partition_for_key(key_parts: &[String], partitions: usize) -> usize {
     (stable_key_hash(key_parts) % total_partitions as u64) as usize
 }
 
+/// Mixes a depth-dependent salt into `stable_key_hash` so a re-partitioned
+/// sub-level hashes keys differently than its parent, while remaining just as
+/// deterministic.
+fn stable_key_hash_with_salt(key_parts: &[String], salt: u64) -> u64 {
+    const SALT_MULTIPLIER: u64 = 0x9e37_79b9_7f4a_7c15;
+    stable_key_hash(key_parts) ^ salt.wrapping_mul(SALT_MULTIPLIER)
+}
+
+/// Salted counterpart to `partition_for_key`, used when recursively
+/// re-partitioning an oversized partition.
+fn partition_for_key_salted(key_parts: &[String], partitions: usize, salt: u64) -> usize {
+    let total_partitions = partitions.max(1);
+    (stable_key_hash_with_salt(key_parts, salt) % total_partitions as u64) as usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpillCodec {
+    Plain,
+    #[cfg(feature = "gzip-spill")]
+    Gzip,
+}
+
+impl SpillCodec {
+    fn active() -> Self {
+        #[cfg(feature = "gzip-spill")]
+        {
+            SpillCodec::Gzip
+        }
+        #[cfg(not(feature = "gzip-spill"))]
+        {
+            SpillCodec::Plain
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PartitionBuffer {
+    lines: Vec<String>,
+    buffered_bytes: usize,
+    flushed_codec: Option<SpillCodec>,
+}
+
+/// A partition-addressed spill area backed by a temp directory. Rows are kept
+/// in memory per partition until the buffer crosses `SpillPolicy::spill_bytes_limit`,
+/// at which point the buffer is flushed to disk as one gzip member (when built
+/// with the `gzip-spill` feature) or raw JSONL. Partitions that never cross the
+/// threshold never touch disk at all.
 #[derive(Debug)]
 pub struct TempDirSpill {
     root: TempDir,
     partitions: usize,
+    policy: SpillPolicy,
+    buffers_base: std::cell::RefCell<Vec<PartitionBuffer>>,
+    buffers_a: std::cell::RefCell<Vec<PartitionBuffer>>,
+    buffers_b: std::cell::RefCell<Vec<PartitionBuffer>>,
+    bytes_spilled: std::cell::Cell<u64>,
+}
+
+fn disk_reserve_violation(root: &Path, reserved_disk_ratio: f64) -> Option<String> {
+    let total = fs2::total_space(root).ok()?;
+    let available = fs2::available_space(root).ok()?;
+    let reserved = (total as f64 * reserved_disk_ratio) as u64;
+    if available < reserved {
+        Some(format!(
+            "insufficient free disk space at {}: {available} bytes available, {reserved} bytes reserved (ratio {reserved_disk_ratio})",
+            root.display()
+        ))
+    } else {
+        None
+    }
 }
 
 impl TempDirSpill {
-    pub fn new(partitions: usize) -> Result<Self, EngineError> {
+    pub fn new(partitions: usize, policy: SpillPolicy) -> Result<Self, EngineError> {
         if partitions == 0 {
             return Err(EngineError::Storage(
                 "partitions must be greater than zero".to_string(),
             ));
         }
+        let base = std::env::temp_dir();
+        if let Some(message) = disk_reserve_violation(&base, policy.reserved_disk_ratio) {
+            return Err(EngineError::Storage(message));
+        }
         let root = tempfile::tempdir().map_err(|err| EngineError::Storage(err.to_string()))?;
-        Ok(Self { root, partitions })
+        Ok(Self {
+            root,
+            partitions,
+            policy,
+            buffers_base: std::cell::RefCell::new(
+                (0..partitions)
+                    .map(|_| PartitionBuffer::default())
+                    .collect(),
+            ),
+            buffers_a: std::cell::RefCell::new(
+                (0..partitions)
+                    .map(|_| PartitionBuffer::default())
+                    .collect(),
+            ),
+            buffers_b: std::cell::RefCell::new(
+                (0..partitions)
+                    .map(|_| PartitionBuffer::default())
+                    .collect(),
+            ),
+            bytes_spilled: std::cell::Cell::new(0),
+        })
     }
 
     pub fn partitions(&self) -> usize {
@@ -110,8 +293,12 @@ impl TempDirSpill {
         self.root.path()
     }
 
+    pub fn bytes_spilled(&self) -> u64 {
+        self.bytes_spilled.get()
+    }
+
     fn validate(&self, side: &str, partition_id: usize) -> Result<(), EngineError> {
-        if side != "a" && side != "b" {
+        if side != "base" && side != "a" && side != "b" {
             return Err(EngineError::Storage(format!("invalid side: {side}")));
         }
         if partition_id >= self.partitions {
@@ -135,36 +322,247 @@ impl TempDirSpill {
             .join(format!("{side}_{partition_id}.jsonl")))
     }
 
+    fn buffers_for(&self, side: &str) -> &std::cell::RefCell<Vec<PartitionBuffer>> {
+        match side {
+            "base" => &self.buffers_base,
+            "a" => &self.buffers_a,
+            _ => &self.buffers_b,
+        }
+    }
+
     pub fn append_line(
         &self,
         side: &str,
         partition_id: usize,
         line: &str,
     ) -> Result<(), EngineError> {
+        self.validate(side, partition_id)?;
+        let should_flush = {
+            let mut buffers = self.buffers_for(side).borrow_mut();
+            let buffer = &mut buffers[partition_id];
+            buffer.lines.push(line.to_string());
+            buffer.buffered_bytes += line.len() + 1;
+            buffer.buffered_bytes >= self.policy.spill_bytes_limit
+        };
+        if should_flush {
+            self.flush_partition(side, partition_id)?;
+        }
+        Ok(())
+    }
+
+    fn flush_partition(&self, side: &str, partition_id: usize) -> Result<(), EngineError> {
+        let pending = {
+            let mut buffers = self.buffers_for(side).borrow_mut();
+            std::mem::take(&mut buffers[partition_id].lines)
+        };
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut body = String::new();
+        for line in &pending {
+            body.push_str(line);
+            body.push('\n');
+        }
+
         let path = self.partition_path(side, partition_id)?;
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&path)
-            .map_err(|err| {
-                EngineError::Storage(format!("failed to open {}: {err}", path.display()))
-            })?;
-        writeln!(file, "{line}").map_err(|err| {
-            EngineError::Storage(format!("failed to write {}: {err}", path.display()))
-        })?;
+        let written = write_spill_chunk(&path, &body)?;
+
+        let mut buffers = self.buffers_for(side).borrow_mut();
+        buffers[partition_id].buffered_bytes = 0;
+        buffers[partition_id].flushed_codec = Some(SpillCodec::active());
+        drop(buffers);
+        self.bytes_spilled.set(self.bytes_spilled.get() + written);
         Ok(())
     }
 
     pub fn read_partition(&self, side: &str, partition_id: usize) -> Result<String, EngineError> {
-        let path = self.partition_path(side, partition_id)?;
-        fs::read_to_string(&path).map_err(|err| {
-            EngineError::Storage(format!("failed to read {}: {err}", path.display()))
+        self.validate(side, partition_id)?;
+        let (flushed_codec, pending_lines) = {
+            let buffers = self.buffers_for(side).borrow();
+            let buffer = &buffers[partition_id];
+            (buffer.flushed_codec, buffer.lines.clone())
+        };
+
+        let mut content = match flushed_codec {
+            None => String::new(),
+            Some(codec) => {
+                let path = self.partition_path(side, partition_id)?;
+                read_spill_chunk(&path, codec)?
+            }
+        };
+        for line in pending_lines {
+            content.push_str(&line);
+            content.push('\n');
+        }
+        Ok(content)
+    }
+}
+
+/// Partition-addressed storage for spilled rows. Abstracts over *where*
+/// partitions actually live, so the partitioning and diffing logic can run
+/// against local temp disk, plain memory (tests, WASM), or — eventually — a
+/// remote backend, without change.
+pub trait SpillStore {
+    fn partitions(&self) -> usize;
+    fn append_line(&self, side: &str, partition_id: usize, line: &str) -> Result<(), EngineError>;
+    fn read_partition(&self, side: &str, partition_id: usize) -> Result<String, EngineError>;
+    fn bytes_spilled(&self) -> u64;
+
+    fn has_partition(&self, side: &str, partition_id: usize) -> Result<bool, EngineError> {
+        Ok(!self.read_partition(side, partition_id)?.trim().is_empty())
+    }
+}
+
+impl SpillStore for TempDirSpill {
+    fn partitions(&self) -> usize {
+        TempDirSpill::partitions(self)
+    }
+
+    fn append_line(&self, side: &str, partition_id: usize, line: &str) -> Result<(), EngineError> {
+        TempDirSpill::append_line(self, side, partition_id, line)
+    }
+
+    fn read_partition(&self, side: &str, partition_id: usize) -> Result<String, EngineError> {
+        TempDirSpill::read_partition(self, side, partition_id)
+    }
+
+    fn bytes_spilled(&self) -> u64 {
+        TempDirSpill::bytes_spilled(self)
+    }
+}
+
+/// A pure in-memory `SpillStore`, useful for tests and WASM builds where a
+/// local temp directory is unavailable or undesirable. Never reports any
+/// bytes spilled, since it never touches disk.
+#[derive(Debug, Default)]
+pub struct MemorySpill {
+    partitions: usize,
+    buffers: std::cell::RefCell<HashMap<(String, usize), Vec<String>>>,
+}
+
+impl MemorySpill {
+    pub fn new(partitions: usize) -> Result<Self, EngineError> {
+        if partitions == 0 {
+            return Err(EngineError::Storage(
+                "partitions must be greater than zero".to_string(),
+            ));
+        }
+        Ok(Self {
+            partitions,
+            buffers: std::cell::RefCell::new(HashMap::new()),
         })
     }
+
+    fn validate(&self, side: &str, partition_id: usize) -> Result<(), EngineError> {
+        if side != "base" && side != "a" && side != "b" {
+            return Err(EngineError::Storage(format!("invalid side: {side}")));
+        }
+        if partition_id >= self.partitions {
+            return Err(EngineError::Storage(format!(
+                "partition out of range: {partition_id} (total {})",
+                self.partitions
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl SpillStore for MemorySpill {
+    fn partitions(&self) -> usize {
+        self.partitions
+    }
+
+    fn append_line(&self, side: &str, partition_id: usize, line: &str) -> Result<(), EngineError> {
+        self.validate(side, partition_id)?;
+        self.buffers
+            .borrow_mut()
+            .entry((side.to_string(), partition_id))
+            .or_default()
+            .push(line.to_string());
+        Ok(())
+    }
+
+    fn read_partition(&self, side: &str, partition_id: usize) -> Result<String, EngineError> {
+        self.validate(side, partition_id)?;
+        let buffers = self.buffers.borrow();
+        let mut content = String::new();
+        if let Some(lines) = buffers.get(&(side.to_string(), partition_id)) {
+            for line in lines {
+                content.push_str(line);
+                content.push('\n');
+            }
+        }
+        Ok(content)
+    }
+
+    fn bytes_spilled(&self) -> u64 {
+        0
+    }
+}
+
+#[cfg(not(feature = "gzip-spill"))]
+fn write_spill_chunk(path: &Path, body: &str) -> Result<u64, EngineError> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| EngineError::Storage(format!("failed to open {}: {err}", path.display())))?;
+    file.write_all(body.as_bytes()).map_err(|err| {
+        EngineError::Storage(format!("failed to write {}: {err}", path.display()))
+    })?;
+    Ok(body.len() as u64)
+}
+
+#[cfg(feature = "gzip-spill")]
+fn write_spill_chunk(path: &Path, body: &str) -> Result<u64, EngineError> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| EngineError::Storage(format!("failed to open {}: {err}", path.display())))?;
+    // Gzip members concatenate cleanly: MultiGzDecoder reads each flush's
+    // member back to back, so appending a fresh member per flush is safe.
+    let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    encoder.write_all(body.as_bytes()).map_err(|err| {
+        EngineError::Storage(format!("failed to write {}: {err}", path.display()))
+    })?;
+    let file = encoder.finish().map_err(|err| {
+        EngineError::Storage(format!("failed to finish {}: {err}", path.display()))
+    })?;
+    file.metadata()
+        .map(|metadata| metadata.len())
+        .map_err(|err| EngineError::Storage(format!("failed to stat {}: {err}", path.display())))
+}
+
+#[cfg(not(feature = "gzip-spill"))]
+fn read_spill_chunk(path: &Path, _codec: SpillCodec) -> Result<String, EngineError> {
+    fs::read_to_string(path)
+        .map_err(|err| EngineError::Storage(format!("failed to read {}: {err}", path.display())))
+}
+
+#[cfg(feature = "gzip-spill")]
+fn read_spill_chunk(path: &Path, codec: SpillCodec) -> Result<String, EngineError> {
+    match codec {
+        SpillCodec::Plain => fs::read_to_string(path).map_err(|err| {
+            EngineError::Storage(format!("failed to read {}: {err}", path.display()))
+        }),
+        SpillCodec::Gzip => {
+            let file = fs::File::open(path).map_err(|err| {
+                EngineError::Storage(format!("failed to open {}: {err}", path.display()))
+            })?;
+            let mut decoder = flate2::read::MultiGzDecoder::new(file);
+            let mut content = String::new();
+            decoder.read_to_string(&mut content).map_err(|err| {
+                EngineError::Storage(format!("failed to decompress {}: {err}", path.display()))
+            })?;
+            Ok(content)
+        }
+    }
 }
 
 pub fn spill_json_record(
-    spill: &TempDirSpill,
+    spill: &dyn SpillStore,
     side: &str,
     key_parts: &[String],
     row: &Value,
@@ -183,106 +581,102 @@ pub struct SpillRecord {
     pub row: BTreeMap<String, String>,
 }
 
-pub fn read_spill_records(
-    spill: &TempDirSpill,
-    side: &str,
-    partition_id: usize,
-) -> Result<Vec<SpillRecord>, EngineError> {
-    let path = spill.partition_path(side, partition_id)?;
-    if !path.exists() {
-        return Ok(Vec::new());
-    }
+fn parse_spill_line(label: &str, line_idx: usize, line: &str) -> Result<SpillRecord, EngineError> {
+    let value: Value = serde_json::from_str(line).map_err(|err| {
+        EngineError::Storage(format!(
+            "failed to parse {label} line {}: {err}",
+            line_idx + 1
+        ))
+    })?;
+    let object = value.as_object().ok_or_else(|| {
+        EngineError::Storage(format!(
+            "invalid spill record in {label} line {}: expected object",
+            line_idx + 1
+        ))
+    })?;
 
-    let content = spill.read_partition(side, partition_id)?;
-    let mut records: Vec<SpillRecord> = Vec::new();
-    for (line_idx, line) in content.lines().enumerate() {
-        if line.trim().is_empty() {
-            continue;
-        }
-        let value: Value = serde_json::from_str(line).map_err(|err| {
+    let key = object
+        .get("key")
+        .and_then(Value::as_array)
+        .ok_or_else(|| {
             EngineError::Storage(format!(
-                "failed to parse {} line {}: {err}",
-                path.display(),
+                "invalid spill record in {label} line {}: missing key",
                 line_idx + 1
             ))
-        })?;
-        let object = value.as_object().ok_or_else(|| {
+        })?
+        .iter()
+        .map(|item| {
+            item.as_str().map(ToString::to_string).ok_or_else(|| {
+                EngineError::Storage(format!(
+                    "invalid spill record in {label} line {}: key entries must be strings",
+                    line_idx + 1
+                ))
+            })
+        })
+        .collect::<Result<Vec<String>, EngineError>>()?;
+
+    let row_index = object
+        .get("row_index")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| {
+            EngineError::Storage(format!(
+                "invalid spill record in {label} line {}: missing row_index",
+                line_idx + 1
+            ))
+        })? as usize;
+
+    let row_object = object
+        .get("row")
+        .and_then(Value::as_object)
+        .ok_or_else(|| {
             EngineError::Storage(format!(
-                "invalid spill record in {} line {}: expected object",
-                path.display(),
+                "invalid spill record in {label} line {}: missing row object",
                 line_idx + 1
             ))
         })?;
 
-        let key = object
-            .get("key")
-            .and_then(Value::as_array)
-            .ok_or_else(|| {
-                EngineError::Storage(format!(
-                    "invalid spill record in {} line {}: missing key",
-                    path.display(),
-                    line_idx + 1
-                ))
-            })?
-            .iter()
-            .map(|item| {
-                item.as_str().map(ToString::to_string).ok_or_else(|| {
-                    EngineError::Storage(format!(
-                        "invalid spill record in {} line {}: key entries must be strings",
-                        path.display(),
-                        line_idx + 1
-                    ))
-                })
-            })
-            .collect::<Result<Vec<String>, EngineError>>()?;
+    let mut row = BTreeMap::new();
+    for (column, value) in row_object {
+        let string_value = value.as_str().ok_or_else(|| {
+            EngineError::Storage(format!(
+                "invalid spill record in {label} line {}: row values must be strings",
+                line_idx + 1
+            ))
+        })?;
+        row.insert(column.clone(), string_value.to_string());
+    }
 
-        let row_index = object
-            .get("row_index")
-            .and_then(Value::as_u64)
-            .ok_or_else(|| {
-                EngineError::Storage(format!(
-                    "invalid spill record in {} line {}: missing row_index",
-                    path.display(),
-                    line_idx + 1
-                ))
-            })? as usize;
+    Ok(SpillRecord {
+        key,
+        row_index,
+        row,
+    })
+}
 
-        let row_object = object
-            .get("row")
-            .and_then(Value::as_object)
-            .ok_or_else(|| {
-                EngineError::Storage(format!(
-                    "invalid spill record in {} line {}: missing row object",
-                    path.display(),
-                    line_idx + 1
-                ))
-            })?;
+pub fn read_spill_records(
+    spill: &dyn SpillStore,
+    side: &str,
+    partition_id: usize,
+) -> Result<Vec<SpillRecord>, EngineError> {
+    let label = format!("{side}/{partition_id}");
+    let content = spill.read_partition(side, partition_id)?;
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
 
-        let mut row = BTreeMap::new();
-        for (column, value) in row_object {
-            let string_value = value.as_str().ok_or_else(|| {
-                EngineError::Storage(format!(
-                    "invalid spill record in {} line {}: row values must be strings",
-                    path.display(),
-                    line_idx + 1
-                ))
-            })?;
-            row.insert(column.clone(), string_value.to_string());
+    let mut records: Vec<SpillRecord> = Vec::new();
+    for (line_idx, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
         }
-
-        records.push(SpillRecord {
-            key,
-            row_index,
-            row,
-        });
+        records.push(parse_spill_line(&label, line_idx, line)?);
     }
 
     Ok(records)
 }
 
-#[derive(Debug)]
 pub struct PartitionManifest {
-    pub spill: TempDirSpill,
+    pub spill: Box<dyn SpillStore>,
     pub columns_a: Vec<String>,
     pub columns_b: Vec<String>,
     pub compare_columns: Vec<String>,
@@ -292,6 +686,20 @@ pub struct PartitionManifest {
     pub partition_rows_b: Vec<usize>,
 }
 
+impl std::fmt::Debug for PartitionManifest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PartitionManifest")
+            .field("columns_a", &self.columns_a)
+            .field("columns_b", &self.columns_b)
+            .field("compare_columns", &self.compare_columns)
+            .field("row_count_a", &self.row_count_a)
+            .field("row_count_b", &self.row_count_b)
+            .field("partition_rows_a", &self.partition_rows_a)
+            .field("partition_rows_b", &self.partition_rows_b)
+            .finish_non_exhaustive()
+    }
+}
+
 fn diff_error(code: &'static str, message: impl Into<String>) -> EngineError {
     EngineError::Diff(DiffError::new(code, message))
 }
@@ -317,10 +725,36 @@ fn validate_header(header: &[String], side: &str) -> Result<(), EngineError> {
     Ok(())
 }
 
-fn open_csv_reader(path: &Path, side: &str) -> Result<Reader<std::fs::File>, EngineError> {
+fn dialect_terminator(options: &DiffOptions) -> csv::Terminator {
+    match options.terminator {
+        Some(byte) => csv::Terminator::Any(byte),
+        None => csv::Terminator::CRLF,
+    }
+}
+
+fn dialect_trim(options: &DiffOptions) -> csv::Trim {
+    match options.trim {
+        diffly_core::CsvTrim::None => csv::Trim::None,
+        diffly_core::CsvTrim::Headers => csv::Trim::Headers,
+        diffly_core::CsvTrim::Fields => csv::Trim::Fields,
+        diffly_core::CsvTrim::All => csv::Trim::All,
+    }
+}
+
+fn open_csv_reader(
+    path: &Path,
+    side: &str,
+    options: &DiffOptions,
+) -> Result<Reader<std::fs::File>, EngineError> {
     ReaderBuilder::new()
         .has_headers(false)
         .flexible(true)
+        .delimiter(options.delimiter)
+        .quote(options.quote)
+        .escape(options.escape)
+        .double_quote(options.double_quote)
+        .terminator(dialect_terminator(options))
+        .trim(dialect_trim(options))
         .from_path(path)
         .map_err(|err| diff_error("csv_open_error", format!("Failed to open {side}: {err}")))
 }
@@ -399,12 +833,16 @@ fn key_indices(header: &[String], key_columns: &[String]) -> Result<Vec<usize>,
 
 fn record_to_json_object(header: &[String], record: &csv::StringRecord) -> Value {
     let mut map = Map::new();
-    for (col, value) in header.iter().zip(record.iter()) {
-        map.insert(col.clone(), Value::String(value.to_string()));
+    for (idx, col) in header.iter().enumerate() {
+        map.insert(
+            col.clone(),
+            Value::String(record.get(idx).unwrap_or_default().to_string()),
+        );
     }
     Value::Object(map)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn partition_one_side(
     side_path: &Path,
     side_tag: &str,
@@ -412,11 +850,12 @@ fn partition_one_side(
     header: &[String],
     key_columns: &[String],
     key_indexes: &[usize],
-    spill: &TempDirSpill,
+    spill: &dyn SpillStore,
     partition_counts: &mut [usize],
+    options: &DiffOptions,
 ) -> Result<usize, EngineError> {
     let width = header.len();
-    let mut reader = open_csv_reader(side_path, side_label)?;
+    let mut reader = open_csv_reader(side_path, side_label, options)?;
     let mut records = reader.records();
 
     // Header already validated in the preflight pass; consume it before streaming rows.
@@ -445,7 +884,7 @@ fn partition_one_side(
             )
         })?;
 
-        if record.len() != width {
+        if record.len() != width && !options.flexible {
             return Err(diff_error(
                 "row_width_mismatch",
                 format!(
@@ -488,39 +927,467 @@ pub fn partition_inputs_to_spill(
     b_path: &Path,
     options: &DiffOptions,
     partitions: usize,
+    spill_policy: SpillPolicy,
 ) -> Result<PartitionManifest, EngineError> {
-    let mut a_reader = open_csv_reader(a_path, "A")?;
-    let mut b_reader = open_csv_reader(b_path, "B")?;
-    let columns_a = read_header(&mut a_reader, a_path, "A")?;
-    let columns_b = read_header(&mut b_reader, b_path, "B")?;
-    let compare_columns = comparison_columns(&columns_a, &columns_b, options.header_mode)?;
+    let spill: Box<dyn SpillStore> = Box::new(TempDirSpill::new(partitions, spill_policy)?);
+    partition_inputs_to_spill_with_store(a_path, b_path, options, spill)
+}
 
-    let key_indices_a = key_indices(&columns_a, &options.key_columns)?;
-    let key_indices_b = key_indices(&columns_b, &options.key_columns)?;
+/// Same as `partition_inputs_to_spill`, but takes an already-constructed
+/// `SpillStore` instead of always standing up a `TempDirSpill`. Lets callers
+/// swap in `MemorySpill` (or any other backend) without touching the
+/// partitioning logic itself.
+pub fn partition_inputs_to_spill_with_store(
+    a_path: &Path,
+    b_path: &Path,
+    options: &DiffOptions,
+    spill: Box<dyn SpillStore>,
+) -> Result<PartitionManifest, EngineError> {
+    let source_a: Box<dyn RecordSource> = Box::new(CsvRecordSource::open(a_path, "A", options)?);
+    let source_b: Box<dyn RecordSource> = Box::new(CsvRecordSource::open(b_path, "B", options)?);
+    partition_sources_to_spill_with_store(source_a, source_b, options, spill)
+}
 
-    let spill = TempDirSpill::new(partitions)?;
-    let mut partition_rows_a = vec![0usize; spill.partitions()];
-    let mut partition_rows_b = vec![0usize; spill.partitions()];
+/// Same partitioning as `partition_inputs_to_spill`, but for `join_partitioned_from_manifest`:
+/// a join matches rows solely on `key_columns`, so A and B are free to carry
+/// entirely different non-key columns (disambiguated with `_a`/`_b` suffixes
+/// at join time) — unlike a diff, which needs matching schemas to compare
+/// cell-by-cell. Skips the `comparison_columns` equality check accordingly;
+/// `key_indices` below still fails fast if a key column is missing from
+/// either side.
+pub fn partition_inputs_to_spill_for_join(
+    a_path: &Path,
+    b_path: &Path,
+    options: &DiffOptions,
+    partitions: usize,
+    spill_policy: SpillPolicy,
+) -> Result<PartitionManifest, EngineError> {
+    let spill: Box<dyn SpillStore> = Box::new(TempDirSpill::new(partitions, spill_policy)?);
+    let source_a: Box<dyn RecordSource> = Box::new(CsvRecordSource::open(a_path, "A", options)?);
+    let source_b: Box<dyn RecordSource> = Box::new(CsvRecordSource::open(b_path, "B", options)?);
+    partition_sources_to_spill_with_store_impl(source_a, source_b, options, spill, false)
+}
 
-    let row_count_a = partition_one_side(
-        a_path,
-        "a",
-        "A",
-        &columns_a,
-        &options.key_columns,
-        &key_indices_a,
-        &spill,
-        &mut partition_rows_a,
-    )?;
-    let row_count_b = partition_one_side(
-        b_path,
-        "b",
-        "B",
-        &columns_b,
-        &options.key_columns,
-        &key_indices_b,
-        &spill,
-        &mut partition_rows_b,
+/// One decoded input row, paired with its 1-based position in the source
+/// (used in error messages the same way CSV row numbers are today).
+pub struct SourceRow {
+    pub row_index: usize,
+    pub row: BTreeMap<String, String>,
+}
+
+/// Decodes one side of a diff into a resolved header and a stream of rows,
+/// decoupling the partitioning/spill engine from any one file format. CSV,
+/// NDJSON, and Parquet sources all implement this so they can be diffed
+/// interchangeably — e.g. a CSV export against a Parquet snapshot of the same
+/// table, with no manual conversion step. `header` must be called, and its
+/// result held onto by the caller, before the first `next_row` call.
+pub trait RecordSource {
+    fn header(&mut self) -> Result<Vec<String>, EngineError>;
+    fn next_row(&mut self) -> Result<Option<SourceRow>, EngineError>;
+}
+
+/// `RecordSource` over a CSV file, reusing the same reader for the header and
+/// every row so the file is only opened once.
+pub struct CsvRecordSource {
+    reader: Reader<std::fs::File>,
+    path: std::path::PathBuf,
+    label: String,
+    header: Option<Vec<String>>,
+    next_row_index: usize,
+    flexible: bool,
+}
+
+impl CsvRecordSource {
+    pub fn open(path: &Path, label: &str, options: &DiffOptions) -> Result<Self, EngineError> {
+        Ok(Self {
+            reader: open_csv_reader(path, label, options)?,
+            path: path.to_path_buf(),
+            label: label.to_string(),
+            header: None,
+            next_row_index: 2,
+            flexible: options.flexible,
+        })
+    }
+}
+
+impl RecordSource for CsvRecordSource {
+    fn header(&mut self) -> Result<Vec<String>, EngineError> {
+        if let Some(header) = &self.header {
+            return Ok(header.clone());
+        }
+        let header = read_header(&mut self.reader, &self.path, &self.label)?;
+        self.header = Some(header.clone());
+        Ok(header)
+    }
+
+    fn next_row(&mut self) -> Result<Option<SourceRow>, EngineError> {
+        let header = self.header.clone().ok_or_else(|| {
+            EngineError::Storage(format!(
+                "{} read out of order: header() must be called first",
+                self.label
+            ))
+        })?;
+        let row_index = self.next_row_index;
+        let mut records = self.reader.records();
+        let record = match records.next() {
+            None => return Ok(None),
+            Some(result) => result.map_err(|err| {
+                diff_error(
+                    "csv_parse_error",
+                    format!(
+                        "Failed to parse {} at CSV row {row_index}: {err}",
+                        self.label
+                    ),
+                )
+            })?,
+        };
+
+        if record.len() != header.len() && !self.flexible {
+            return Err(diff_error(
+                "row_width_mismatch",
+                format!(
+                    "Row width mismatch in {} at CSV row {row_index}: expected {}, got {}",
+                    self.label,
+                    header.len(),
+                    record.len()
+                ),
+            ));
+        }
+
+        self.next_row_index += 1;
+        let row = header
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| {
+                (
+                    column.clone(),
+                    record.get(idx).unwrap_or_default().to_string(),
+                )
+            })
+            .collect();
+        Ok(Some(SourceRow { row_index, row }))
+    }
+}
+
+fn json_value_to_field_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Number(number) => number.to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// `RecordSource` over newline-delimited JSON, one object per line. The
+/// header is the sorted key set of the first non-blank line (`BTreeMap`
+/// already yields keys in that order); later rows may omit or add keys, and
+/// values of any JSON type are canonicalized to strings.
+pub struct NdjsonRecordSource {
+    lines: std::io::Lines<std::io::BufReader<std::fs::File>>,
+    label: String,
+    header: Option<Vec<String>>,
+    next_row_index: usize,
+    pending_first_row: Option<SourceRow>,
+}
+
+impl NdjsonRecordSource {
+    pub fn open(path: &Path, label: &str) -> Result<Self, EngineError> {
+        let file = std::fs::File::open(path).map_err(|err| {
+            diff_error("csv_open_error", format!("Failed to open {label}: {err}"))
+        })?;
+        Ok(Self {
+            lines: std::io::BufRead::lines(std::io::BufReader::new(file)),
+            label: label.to_string(),
+            header: None,
+            next_row_index: 1,
+            pending_first_row: None,
+        })
+    }
+
+    fn parse_line(
+        &self,
+        row_index: usize,
+        line: &str,
+    ) -> Result<BTreeMap<String, String>, EngineError> {
+        let value: Value = serde_json::from_str(line).map_err(|err| {
+            diff_error(
+                "ndjson_parse_error",
+                format!("Failed to parse {} at line {row_index}: {err}", self.label),
+            )
+        })?;
+        let object = value.as_object().ok_or_else(|| {
+            diff_error(
+                "ndjson_parse_error",
+                format!(
+                    "Expected a JSON object in {} at line {row_index}",
+                    self.label
+                ),
+            )
+        })?;
+        Ok(object
+            .iter()
+            .map(|(key, val)| (key.clone(), json_value_to_field_string(val)))
+            .collect())
+    }
+}
+
+impl RecordSource for NdjsonRecordSource {
+    fn header(&mut self) -> Result<Vec<String>, EngineError> {
+        if let Some(header) = &self.header {
+            return Ok(header.clone());
+        }
+        loop {
+            let row_index = self.next_row_index;
+            self.next_row_index += 1;
+            let line = match self.lines.next() {
+                None => {
+                    return Err(diff_error(
+                        "empty_file",
+                        format!("{} file is empty", self.label),
+                    ))
+                }
+                Some(line) => line.map_err(|err| {
+                    EngineError::Storage(format!("failed to read {}: {err}", self.label))
+                })?,
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = self.parse_line(row_index, &line)?;
+            let header: Vec<String> = row.keys().cloned().collect();
+            self.header = Some(header.clone());
+            self.pending_first_row = Some(SourceRow { row_index, row });
+            return Ok(header);
+        }
+    }
+
+    fn next_row(&mut self) -> Result<Option<SourceRow>, EngineError> {
+        if self.header.is_none() {
+            self.header()?;
+        }
+        if let Some(row) = self.pending_first_row.take() {
+            return Ok(Some(row));
+        }
+        loop {
+            let row_index = self.next_row_index;
+            let line = match self.lines.next() {
+                None => return Ok(None),
+                Some(line) => line.map_err(|err| {
+                    EngineError::Storage(format!("failed to read {}: {err}", self.label))
+                })?,
+            };
+            self.next_row_index += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = self.parse_line(row_index, &line)?;
+            return Ok(Some(SourceRow { row_index, row }));
+        }
+    }
+}
+
+/// `RecordSource` over a Parquet/Arrow file: each row group is materialized
+/// into row maps, casting every typed column to its canonical string
+/// representation. Gated behind the `parquet-source` feature since it pulls
+/// in the `parquet`/`arrow` crates, which most installs never need.
+#[cfg(feature = "parquet-source")]
+pub struct ParquetRecordSource {
+    reader: parquet::arrow::arrow_reader::ParquetRecordBatchReader,
+    header: Vec<String>,
+    current_batch: Option<arrow_array::RecordBatch>,
+    current_batch_row: usize,
+    next_row_index: usize,
+    label: String,
+}
+
+#[cfg(feature = "parquet-source")]
+impl ParquetRecordSource {
+    pub fn open(path: &Path, label: &str) -> Result<Self, EngineError> {
+        let file = std::fs::File::open(path).map_err(|err| {
+            diff_error("csv_open_error", format!("Failed to open {label}: {err}"))
+        })?;
+        let builder = parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(file)
+            .map_err(|err| {
+                diff_error(
+                    "parquet_open_error",
+                    format!("Failed to open {label}: {err}"),
+                )
+            })?;
+        let header: Vec<String> = builder
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        let reader = builder.build().map_err(|err| {
+            diff_error(
+                "parquet_open_error",
+                format!("Failed to read {label}: {err}"),
+            )
+        })?;
+        Ok(Self {
+            reader,
+            header,
+            current_batch: None,
+            current_batch_row: 0,
+            next_row_index: 1,
+            label: label.to_string(),
+        })
+    }
+
+    fn advance_batch(&mut self) -> Result<bool, EngineError> {
+        match self.reader.next() {
+            None => Ok(false),
+            Some(batch) => {
+                let batch = batch.map_err(|err| {
+                    diff_error(
+                        "parquet_parse_error",
+                        format!("Failed to read row group in {}: {err}", self.label),
+                    )
+                })?;
+                self.current_batch = Some(batch);
+                self.current_batch_row = 0;
+                Ok(true)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "parquet-source")]
+fn arrow_value_to_field_string(array: &dyn arrow_array::Array, row: usize) -> String {
+    if array.is_null(row) {
+        return String::new();
+    }
+    arrow_cast::display::array_value_to_string(array, row).unwrap_or_default()
+}
+
+#[cfg(feature = "parquet-source")]
+impl RecordSource for ParquetRecordSource {
+    fn header(&mut self) -> Result<Vec<String>, EngineError> {
+        Ok(self.header.clone())
+    }
+
+    fn next_row(&mut self) -> Result<Option<SourceRow>, EngineError> {
+        loop {
+            let needs_batch = match &self.current_batch {
+                Some(batch) => self.current_batch_row >= batch.num_rows(),
+                None => true,
+            };
+            if needs_batch && !self.advance_batch()? {
+                return Ok(None);
+            }
+            let batch = self.current_batch.as_ref().expect("batch just loaded");
+            if self.current_batch_row >= batch.num_rows() {
+                continue;
+            }
+
+            let row_index = self.next_row_index;
+            self.next_row_index += 1;
+            let mut row = BTreeMap::new();
+            for (col_idx, column_name) in self.header.iter().enumerate() {
+                let array = batch.column(col_idx);
+                row.insert(
+                    column_name.clone(),
+                    arrow_value_to_field_string(array.as_ref(), self.current_batch_row),
+                );
+            }
+            self.current_batch_row += 1;
+            return Ok(Some(SourceRow { row_index, row }));
+        }
+    }
+}
+
+fn partition_one_source(
+    source: &mut dyn RecordSource,
+    side_tag: &str,
+    side_label: &str,
+    key_columns: &[String],
+    spill: &dyn SpillStore,
+    partition_counts: &mut [usize],
+) -> Result<usize, EngineError> {
+    let mut row_count = 0usize;
+    while let Some(SourceRow { row_index, row }) = source.next_row()? {
+        let mut key_parts: Vec<String> = Vec::with_capacity(key_columns.len());
+        for key_column in key_columns {
+            let value = row.get(key_column).cloned().unwrap_or_default();
+            if value.is_empty() {
+                return Err(diff_error(
+                    "missing_key_value",
+                    format!(
+                        "Missing key value in {side_label} at row {row_index} for key column '{key_column}'"
+                    ),
+                ));
+            }
+            key_parts.push(value);
+        }
+
+        let envelope = json!({
+            "key": key_parts.clone(),
+            "row_index": row_index,
+            "row": row_to_value(&row)
+        });
+        let partition_id = spill_json_record(spill, side_tag, &key_parts, &envelope)?;
+        partition_counts[partition_id] += 1;
+        row_count += 1;
+    }
+    Ok(row_count)
+}
+
+/// Same as `partition_inputs_to_spill_with_store`, but accepts any two
+/// `RecordSource`s instead of always reading CSV files from disk — e.g. a
+/// CSV export diffed directly against an NDJSON or Parquet snapshot of the
+/// same table. `columns_a`/`columns_b`/`compare_columns` are resolved exactly
+/// as before since that logic only ever operated on column name lists.
+pub fn partition_sources_to_spill_with_store(
+    source_a: Box<dyn RecordSource>,
+    source_b: Box<dyn RecordSource>,
+    options: &DiffOptions,
+    spill: Box<dyn SpillStore>,
+) -> Result<PartitionManifest, EngineError> {
+    partition_sources_to_spill_with_store_impl(source_a, source_b, options, spill, true)
+}
+
+/// Shared by `partition_sources_to_spill_with_store` (diff: `require_matching_schema`
+/// is true, so A/B must agree on every column) and `partition_inputs_to_spill_for_join`
+/// (join: false, since only the key columns need to line up).
+fn partition_sources_to_spill_with_store_impl(
+    mut source_a: Box<dyn RecordSource>,
+    mut source_b: Box<dyn RecordSource>,
+    options: &DiffOptions,
+    spill: Box<dyn SpillStore>,
+    require_matching_schema: bool,
+) -> Result<PartitionManifest, EngineError> {
+    let columns_a = source_a.header()?;
+    let columns_b = source_b.header()?;
+    let compare_columns = if require_matching_schema {
+        comparison_columns(&columns_a, &columns_b, options.header_mode)?
+    } else {
+        Vec::new()
+    };
+
+    // Fails fast on a missing key column before any rows are spilled.
+    key_indices(&columns_a, &options.key_columns)?;
+    key_indices(&columns_b, &options.key_columns)?;
+
+    let mut partition_rows_a = vec![0usize; spill.partitions()];
+    let mut partition_rows_b = vec![0usize; spill.partitions()];
+
+    let row_count_a = partition_one_source(
+        source_a.as_mut(),
+        "a",
+        "A",
+        &options.key_columns,
+        spill.as_ref(),
+        &mut partition_rows_a,
+    )?;
+    let row_count_b = partition_one_source(
+        source_b.as_mut(),
+        "b",
+        "B",
+        &options.key_columns,
+        spill.as_ref(),
+        &mut partition_rows_b,
     )?;
 
     Ok(PartitionManifest {
@@ -535,6 +1402,163 @@ pub fn partition_inputs_to_spill(
     })
 }
 
+fn comparison_columns3(
+    base_header: &[String],
+    a_header: &[String],
+    b_header: &[String],
+    header_mode: HeaderMode,
+) -> Result<Vec<String>, EngineError> {
+    match header_mode {
+        HeaderMode::Strict => {
+            if base_header != a_header || base_header != b_header {
+                return Err(diff_error(
+                    "header_mismatch",
+                    format!("Header mismatch: Base={base_header:?} A={a_header:?} B={b_header:?}"),
+                ));
+            }
+            Ok(base_header.to_vec())
+        }
+        HeaderMode::Sorted => {
+            let mut base_sorted = base_header.to_vec();
+            let mut a_sorted = a_header.to_vec();
+            let mut b_sorted = b_header.to_vec();
+            base_sorted.sort();
+            a_sorted.sort();
+            b_sorted.sort();
+            if base_sorted != a_sorted || base_sorted != b_sorted {
+                return Err(diff_error(
+                    "header_mismatch",
+                    format!(
+                        "Header mismatch (sorted mode): Base={base_header:?} A={a_header:?} B={b_header:?}"
+                    ),
+                ));
+            }
+            Ok(base_sorted)
+        }
+    }
+}
+
+/// Same partitioning as `partition_inputs_to_spill`, but over three inputs —
+/// `base`, `a`, and `b` — so that a three-way merge can colocate each key's
+/// base/A/B rows in the same partition.
+pub struct PartitionManifest3 {
+    pub spill: Box<dyn SpillStore>,
+    pub columns_base: Vec<String>,
+    pub columns_a: Vec<String>,
+    pub columns_b: Vec<String>,
+    pub compare_columns: Vec<String>,
+    pub row_count_base: usize,
+    pub row_count_a: usize,
+    pub row_count_b: usize,
+    pub partition_rows_base: Vec<usize>,
+    pub partition_rows_a: Vec<usize>,
+    pub partition_rows_b: Vec<usize>,
+}
+
+impl std::fmt::Debug for PartitionManifest3 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PartitionManifest3")
+            .field("columns_base", &self.columns_base)
+            .field("columns_a", &self.columns_a)
+            .field("columns_b", &self.columns_b)
+            .field("compare_columns", &self.compare_columns)
+            .field("row_count_base", &self.row_count_base)
+            .field("row_count_a", &self.row_count_a)
+            .field("row_count_b", &self.row_count_b)
+            .field("partition_rows_base", &self.partition_rows_base)
+            .field("partition_rows_a", &self.partition_rows_a)
+            .field("partition_rows_b", &self.partition_rows_b)
+            .finish_non_exhaustive()
+    }
+}
+
+pub fn partition_inputs3_to_spill(
+    base_path: &Path,
+    a_path: &Path,
+    b_path: &Path,
+    options: &DiffOptions,
+    partitions: usize,
+    spill_policy: SpillPolicy,
+) -> Result<PartitionManifest3, EngineError> {
+    let spill: Box<dyn SpillStore> = Box::new(TempDirSpill::new(partitions, spill_policy)?);
+    partition_inputs3_to_spill_with_store(base_path, a_path, b_path, options, spill)
+}
+
+/// Same as `partition_inputs3_to_spill`, but takes an already-constructed
+/// `SpillStore` instead of always standing up a `TempDirSpill`.
+pub fn partition_inputs3_to_spill_with_store(
+    base_path: &Path,
+    a_path: &Path,
+    b_path: &Path,
+    options: &DiffOptions,
+    spill: Box<dyn SpillStore>,
+) -> Result<PartitionManifest3, EngineError> {
+    let mut base_reader = open_csv_reader(base_path, "Base", options)?;
+    let mut a_reader = open_csv_reader(a_path, "A", options)?;
+    let mut b_reader = open_csv_reader(b_path, "B", options)?;
+    let columns_base = read_header(&mut base_reader, base_path, "Base")?;
+    let columns_a = read_header(&mut a_reader, a_path, "A")?;
+    let columns_b = read_header(&mut b_reader, b_path, "B")?;
+    let compare_columns =
+        comparison_columns3(&columns_base, &columns_a, &columns_b, options.header_mode)?;
+
+    let key_indices_base = key_indices(&columns_base, &options.key_columns)?;
+    let key_indices_a = key_indices(&columns_a, &options.key_columns)?;
+    let key_indices_b = key_indices(&columns_b, &options.key_columns)?;
+
+    let mut partition_rows_base = vec![0usize; spill.partitions()];
+    let mut partition_rows_a = vec![0usize; spill.partitions()];
+    let mut partition_rows_b = vec![0usize; spill.partitions()];
+
+    let row_count_base = partition_one_side(
+        base_path,
+        "base",
+        "Base",
+        &columns_base,
+        &options.key_columns,
+        &key_indices_base,
+        spill.as_ref(),
+        &mut partition_rows_base,
+        options,
+    )?;
+    let row_count_a = partition_one_side(
+        a_path,
+        "a",
+        "A",
+        &columns_a,
+        &options.key_columns,
+        &key_indices_a,
+        spill.as_ref(),
+        &mut partition_rows_a,
+        options,
+    )?;
+    let row_count_b = partition_one_side(
+        b_path,
+        "b",
+        "B",
+        &columns_b,
+        &options.key_columns,
+        &key_indices_b,
+        spill.as_ref(),
+        &mut partition_rows_b,
+        options,
+    )?;
+
+    Ok(PartitionManifest3 {
+        spill,
+        columns_base,
+        columns_a,
+        columns_b,
+        compare_columns,
+        row_count_base,
+        row_count_a,
+        row_count_b,
+        partition_rows_base,
+        partition_rows_a,
+        partition_rows_b,
+    })
+}
+
 fn key_object(key_columns: &[String], key_values: &[String]) -> Value {
     let mut key = Map::new();
     for (idx, column) in key_columns.iter().enumerate() {
@@ -551,14 +1575,81 @@ fn row_to_value(row: &BTreeMap<String, String>) -> Value {
     Value::Object(value)
 }
 
-fn index_spill_records(
-    records: Vec<SpillRecord>,
-    key_columns: &[String],
-    side: &str,
-) -> Result<HashMap<Vec<String>, SpillRecord>, EngineError> {
-    let mut indexed: HashMap<Vec<String>, SpillRecord> = HashMap::new();
-    for record in records {
-        if let Some(prior) = indexed.get(&record.key) {
+fn apply_matched_row(
+    events: &mut Vec<Value>,
+    key_obj: Value,
+    record_a: &SpillRecord,
+    record_b: &SpillRecord,
+    compare_columns: &[String],
+    options: &DiffOptions,
+) -> bool {
+    let changed_columns: Vec<String> = compare_columns
+        .iter()
+        .filter(|column| {
+            let value_a = record_a
+                .row
+                .get(*column)
+                .map(String::as_str)
+                .unwrap_or_default();
+            let value_b = record_b
+                .row
+                .get(*column)
+                .map(String::as_str)
+                .unwrap_or_default();
+            !values_equal(
+                column,
+                value_a,
+                value_b,
+                options.column_types.get(*column).copied(),
+                options,
+            )
+        })
+        .cloned()
+        .collect();
+
+    if changed_columns.is_empty() {
+        if options.emit_unchanged {
+            events.push(json!({
+                "type": "unchanged",
+                "key": key_obj,
+                "row": row_to_value(&record_a.row)
+            }));
+        }
+        return false;
+    }
+
+    let mut delta = Map::new();
+    for column in &changed_columns {
+        let from = record_a.row.get(column).cloned().unwrap_or_default();
+        let to = record_b.row.get(column).cloned().unwrap_or_default();
+        let mut entry = json!({ "from": from, "to": to });
+        if let Some(segments) =
+            field_diff_segments(options.field_diff, &from, &to, options.field_diff_max_len)
+        {
+            entry["segments"] = Value::Array(segments);
+        }
+        delta.insert(column.clone(), entry);
+    }
+
+    events.push(json!({
+        "type": "changed",
+        "key": key_obj,
+        "changed": changed_columns,
+        "before": row_to_value(&record_a.row),
+        "after": row_to_value(&record_b.row),
+        "delta": Value::Object(delta)
+    }));
+    true
+}
+
+fn index_spill_records(
+    records: Vec<SpillRecord>,
+    key_columns: &[String],
+    side: &str,
+) -> Result<HashMap<Vec<String>, SpillRecord>, EngineError> {
+    let mut indexed: HashMap<Vec<String>, SpillRecord> = HashMap::new();
+    for record in records {
+        if let Some(prior) = indexed.get(&record.key) {
             return Err(diff_error(
                 "duplicate_key",
                 format!(
@@ -571,34 +1662,782 @@ fn index_spill_records(
         }
         indexed.insert(record.key.clone(), record);
     }
-    Ok(indexed)
+    Ok(indexed)
+}
+
+pub fn diff_partitioned_from_manifest(
+    manifest: &PartitionManifest,
+    options: &DiffOptions,
+) -> Result<Vec<Value>, EngineError> {
+    let mut events: Vec<Value> = Vec::new();
+    events.push(json!({
+        "type": "schema",
+        "columns_a": &manifest.columns_a,
+        "columns_b": &manifest.columns_b
+    }));
+
+    let mut rows_total_compared = 0u64;
+    let mut rows_added = 0u64;
+    let mut rows_removed = 0u64;
+    let mut rows_changed = 0u64;
+    let mut rows_unchanged = 0u64;
+
+    for partition_id in 0..manifest.spill.partitions() {
+        let indexed_a = index_spill_records(
+            read_spill_records(manifest.spill.as_ref(), "a", partition_id)?,
+            &options.key_columns,
+            "A",
+        )?;
+        let indexed_b = index_spill_records(
+            read_spill_records(manifest.spill.as_ref(), "b", partition_id)?,
+            &options.key_columns,
+            "B",
+        )?;
+
+        let mut all_keys: Vec<Vec<String>> = indexed_a
+            .keys()
+            .chain(indexed_b.keys())
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        all_keys.sort();
+
+        for key in all_keys {
+            let key_obj = key_object(&options.key_columns, &key);
+            let in_a = indexed_a.get(&key);
+            let in_b = indexed_b.get(&key);
+
+            match (in_a, in_b) {
+                (None, Some(record_b)) => {
+                    rows_added += 1;
+                    events.push(json!({
+                        "type": "added",
+                        "key": key_obj,
+                        "row": row_to_value(&record_b.row)
+                    }));
+                }
+                (Some(record_a), None) => {
+                    rows_removed += 1;
+                    events.push(json!({
+                        "type": "removed",
+                        "key": key_obj,
+                        "row": row_to_value(&record_a.row)
+                    }));
+                }
+                (Some(record_a), Some(record_b)) => {
+                    rows_total_compared += 1;
+                    if apply_matched_row(
+                        &mut events,
+                        key_obj,
+                        record_a,
+                        record_b,
+                        &manifest.compare_columns,
+                        options,
+                    ) {
+                        rows_changed += 1;
+                    } else {
+                        rows_unchanged += 1;
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    events.push(json!({
+        "type": "stats",
+        "rows_total_compared": rows_total_compared,
+        "rows_added": rows_added,
+        "rows_removed": rows_removed,
+        "rows_changed": rows_changed,
+        "rows_unchanged": rows_unchanged,
+        "bytes_spilled": manifest.spill.bytes_spilled()
+    }));
+    Ok(events)
+}
+
+/// Recursion depth `diff_partition_recursive` gives up at, matching an
+/// oversized partition in place rather than re-partitioning forever.
+const MAX_REPARTITION_DEPTH: usize = 6;
+/// Sub-partitions an oversized partition is hash-split into at each
+/// recursive re-partition level.
+const REPARTITION_FANOUT: usize = 4;
+
+/// Running per-diff counters threaded through the recursive partition
+/// matcher, mirroring the locals `diff_partitioned_from_manifest` keeps.
+#[derive(Default)]
+struct DiffCounts {
+    rows_total_compared: u64,
+    rows_added: u64,
+    rows_removed: u64,
+    rows_changed: u64,
+    rows_unchanged: u64,
+}
+
+/// Matches two already-indexed sides of one (sub-)partition and appends the
+/// resulting events, the same key-union walk `diff_partitioned_from_manifest`
+/// does inline.
+fn match_indexed_partition(
+    indexed_a: HashMap<Vec<String>, SpillRecord>,
+    indexed_b: HashMap<Vec<String>, SpillRecord>,
+    key_columns: &[String],
+    compare_columns: &[String],
+    options: &DiffOptions,
+    events: &mut Vec<Value>,
+    counts: &mut DiffCounts,
+) {
+    let mut all_keys: Vec<Vec<String>> = indexed_a
+        .keys()
+        .chain(indexed_b.keys())
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    all_keys.sort();
+
+    for key in all_keys {
+        let key_obj = key_object(key_columns, &key);
+        let in_a = indexed_a.get(&key);
+        let in_b = indexed_b.get(&key);
+
+        match (in_a, in_b) {
+            (None, Some(record_b)) => {
+                counts.rows_added += 1;
+                events.push(json!({
+                    "type": "added",
+                    "key": key_obj,
+                    "row": row_to_value(&record_b.row)
+                }));
+            }
+            (Some(record_a), None) => {
+                counts.rows_removed += 1;
+                events.push(json!({
+                    "type": "removed",
+                    "key": key_obj,
+                    "row": row_to_value(&record_a.row)
+                }));
+            }
+            (Some(record_a), Some(record_b)) => {
+                counts.rows_total_compared += 1;
+                if apply_matched_row(
+                    events,
+                    key_obj,
+                    record_a,
+                    record_b,
+                    compare_columns,
+                    options,
+                ) {
+                    counts.rows_changed += 1;
+                } else {
+                    counts.rows_unchanged += 1;
+                }
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Hash-splits one side's records into `fanout` buckets using the salted key
+/// hash for the given recursion depth.
+fn bucket_by_salted_key(
+    records: Vec<SpillRecord>,
+    fanout: usize,
+    salt: u64,
+) -> Vec<Vec<SpillRecord>> {
+    let mut buckets: Vec<Vec<SpillRecord>> = (0..fanout).map(|_| Vec::new()).collect();
+    for record in records {
+        let bucket = partition_for_key_salted(&record.key, fanout, salt);
+        buckets[bucket].push(record);
+    }
+    buckets
+}
+
+/// GRACE-style recursive partition matcher: indexes and matches both sides
+/// directly once they fit `budget` (or the max recursion depth is hit),
+/// otherwise re-hashes both sides with a depth-salted key hash into
+/// `REPARTITION_FANOUT` sub-partitions and recurses into each. Both sides are
+/// always re-split with the identical scheme, so matching keys stay
+/// colocated at every level.
+#[allow(clippy::too_many_arguments)]
+fn diff_partition_recursive(
+    records_a: Vec<SpillRecord>,
+    records_b: Vec<SpillRecord>,
+    key_columns: &[String],
+    compare_columns: &[String],
+    options: &DiffOptions,
+    budget: usize,
+    depth: usize,
+    events: &mut Vec<Value>,
+    counts: &mut DiffCounts,
+) -> Result<(), EngineError> {
+    let within_budget = records_a.len() <= budget && records_b.len() <= budget;
+    if within_budget || depth >= MAX_REPARTITION_DEPTH {
+        let indexed_a = index_spill_records(records_a, key_columns, "A")?;
+        let indexed_b = index_spill_records(records_b, key_columns, "B")?;
+        match_indexed_partition(
+            indexed_a,
+            indexed_b,
+            key_columns,
+            compare_columns,
+            options,
+            events,
+            counts,
+        );
+        return Ok(());
+    }
+
+    let salt = depth as u64 + 1;
+    let buckets_a = bucket_by_salted_key(records_a, REPARTITION_FANOUT, salt);
+    let buckets_b = bucket_by_salted_key(records_b, REPARTITION_FANOUT, salt);
+
+    let used_buckets = (0..REPARTITION_FANOUT)
+        .filter(|&idx| !buckets_a[idx].is_empty() || !buckets_b[idx].is_empty())
+        .count();
+    if used_buckets <= 1 {
+        // Re-hashing couldn't separate anything further — every record
+        // shares one key (more duplicates on one side than the budget
+        // allows). Match in place; a genuine duplicate surfaces below via
+        // index_spill_records's existing duplicate_key error rather than
+        // recursing forever.
+        let records_a: Vec<SpillRecord> = buckets_a.into_iter().flatten().collect();
+        let records_b: Vec<SpillRecord> = buckets_b.into_iter().flatten().collect();
+        let indexed_a = index_spill_records(records_a, key_columns, "A")?;
+        let indexed_b = index_spill_records(records_b, key_columns, "B")?;
+        match_indexed_partition(
+            indexed_a,
+            indexed_b,
+            key_columns,
+            compare_columns,
+            options,
+            events,
+            counts,
+        );
+        return Ok(());
+    }
+
+    for (bucket_a, bucket_b) in buckets_a.into_iter().zip(buckets_b) {
+        diff_partition_recursive(
+            bucket_a,
+            bucket_b,
+            key_columns,
+            compare_columns,
+            options,
+            budget,
+            depth + 1,
+            events,
+            counts,
+        )?;
+    }
+    Ok(())
+}
+
+/// Same as `diff_partitioned_from_manifest`, but recursively re-partitions
+/// (GRACE-hash style) any partition whose larger side exceeds
+/// `partition_memory_budget` records, so a skewed key distribution can't
+/// exhaust RAM while matching. A budget of `usize::MAX` behaves identically
+/// to `diff_partitioned_from_manifest`.
+pub fn diff_partitioned_from_manifest_with_budget(
+    manifest: &PartitionManifest,
+    options: &DiffOptions,
+    partition_memory_budget: usize,
+) -> Result<Vec<Value>, EngineError> {
+    let mut events: Vec<Value> = Vec::new();
+    events.push(json!({
+        "type": "schema",
+        "columns_a": &manifest.columns_a,
+        "columns_b": &manifest.columns_b
+    }));
+
+    let mut counts = DiffCounts::default();
+
+    for partition_id in 0..manifest.spill.partitions() {
+        let records_a = read_spill_records(manifest.spill.as_ref(), "a", partition_id)?;
+        let records_b = read_spill_records(manifest.spill.as_ref(), "b", partition_id)?;
+        let partition_start = events.len();
+        diff_partition_recursive(
+            records_a,
+            records_b,
+            &options.key_columns,
+            &manifest.compare_columns,
+            options,
+            partition_memory_budget,
+            0,
+            &mut events,
+            &mut counts,
+        )?;
+        // Sub-partitions are matched one after another; restore the sorted
+        // per-partition key order `diff_partitioned_from_manifest` emits.
+        events[partition_start..].sort_by_cached_key(|event| {
+            options
+                .key_columns
+                .iter()
+                .map(|column| event["key"][column].as_str().unwrap_or_default().to_string())
+                .collect::<Vec<String>>()
+        });
+    }
+
+    events.push(json!({
+        "type": "stats",
+        "rows_total_compared": counts.rows_total_compared,
+        "rows_added": counts.rows_added,
+        "rows_removed": counts.rows_removed,
+        "rows_changed": counts.rows_changed,
+        "rows_unchanged": counts.rows_unchanged,
+        "bytes_spilled": manifest.spill.bytes_spilled()
+    }));
+    Ok(events)
+}
+
+/// Rows sorted per in-memory chunk before being written out as a run; bounds
+/// the parsed records held at once, not the partition text read beforehand.
+const EXTERNAL_SORT_CHUNK_ROWS: usize = 10_000;
+
+fn write_sorted_run(
+    run_dir: &Path,
+    side: &str,
+    partition_id: usize,
+    run_id: usize,
+    chunk: &mut Vec<SpillRecord>,
+) -> Result<std::path::PathBuf, EngineError> {
+    chunk.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let run_path = run_dir.join(format!("{side}_{partition_id}_run_{run_id}.jsonl"));
+    let mut file = fs::File::create(&run_path).map_err(|err| {
+        EngineError::Storage(format!("failed to create {}: {err}", run_path.display()))
+    })?;
+    for record in chunk.iter() {
+        let envelope = json!({
+            "key": record.key,
+            "row_index": record.row_index,
+            "row": row_to_value(&record.row)
+        });
+        let encoded = serde_json::to_string(&envelope)
+            .map_err(|err| EngineError::Storage(err.to_string()))?;
+        writeln!(file, "{encoded}").map_err(|err| {
+            EngineError::Storage(format!("failed to write {}: {err}", run_path.display()))
+        })?;
+    }
+    chunk.clear();
+    Ok(run_path)
+}
+
+/// Splits a partition's JSONL file into key-sorted runs of at most
+/// `EXTERNAL_SORT_CHUNK_ROWS` records each, so no more than one chunk of parsed
+/// records is held at a time. The partition's JSONL text itself is read into
+/// memory whole first.
+fn external_sort_partition(
+    spill: &dyn SpillStore,
+    side: &str,
+    partition_id: usize,
+    run_dir: &Path,
+) -> Result<Vec<std::path::PathBuf>, EngineError> {
+    let label = format!("{side}/{partition_id}");
+    let content = spill.read_partition(side, partition_id)?;
+
+    let mut run_paths = Vec::new();
+    let mut chunk: Vec<SpillRecord> = Vec::with_capacity(EXTERNAL_SORT_CHUNK_ROWS);
+    let mut run_id = 0usize;
+
+    for (line_idx, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        chunk.push(parse_spill_line(&label, line_idx, line)?);
+        if chunk.len() >= EXTERNAL_SORT_CHUNK_ROWS {
+            run_paths.push(write_sorted_run(
+                run_dir,
+                side,
+                partition_id,
+                run_id,
+                &mut chunk,
+            )?);
+            run_id += 1;
+        }
+    }
+    if !chunk.is_empty() {
+        run_paths.push(write_sorted_run(
+            run_dir,
+            side,
+            partition_id,
+            run_id,
+            &mut chunk,
+        )?);
+    }
+
+    Ok(run_paths)
+}
+
+/// Reads one sorted run file one record at a time, keeping at most a single
+/// parsed record buffered for the k-way merge to peek at.
+struct RunCursor {
+    lines: std::io::Lines<std::io::BufReader<fs::File>>,
+    path: std::path::PathBuf,
+    line_idx: usize,
+    peeked: Option<SpillRecord>,
+}
+
+impl RunCursor {
+    fn open(path: std::path::PathBuf) -> Result<Self, EngineError> {
+        let file = fs::File::open(&path).map_err(|err| {
+            EngineError::Storage(format!("failed to open {}: {err}", path.display()))
+        })?;
+        let mut cursor = Self {
+            lines: std::io::BufRead::lines(std::io::BufReader::new(file)),
+            path,
+            line_idx: 0,
+            peeked: None,
+        };
+        cursor.advance()?;
+        Ok(cursor)
+    }
+
+    fn advance(&mut self) -> Result<(), EngineError> {
+        self.peeked = None;
+        for line in self.lines.by_ref() {
+            let line = line.map_err(|err| {
+                EngineError::Storage(format!("failed to read {}: {err}", self.path.display()))
+            })?;
+            self.line_idx += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let label = self.path.display().to_string();
+            self.peeked = Some(parse_spill_line(&label, self.line_idx - 1, &line)?);
+            break;
+        }
+        Ok(())
+    }
+}
+
+/// K-way merges a partition's sorted runs for one side into a single
+/// ascending-by-key stream, raising the existing `duplicate_key` error if two
+/// consecutive records share a key. Owns its own scratch temp directory for
+/// run files, independent of whatever `SpillStore` the partition itself was
+/// spilled to.
+struct SortedPartitionStream<'a> {
+    cursors: Vec<RunCursor>,
+    prev: Option<SpillRecord>,
+    key_columns: &'a [String],
+    side_label: &'static str,
+    _run_dir: TempDir,
+}
+
+impl<'a> SortedPartitionStream<'a> {
+    fn open(
+        spill: &dyn SpillStore,
+        side: &str,
+        partition_id: usize,
+        key_columns: &'a [String],
+        side_label: &'static str,
+    ) -> Result<Self, EngineError> {
+        let run_dir = tempfile::tempdir().map_err(|err| EngineError::Storage(err.to_string()))?;
+        let run_paths = external_sort_partition(spill, side, partition_id, run_dir.path())?;
+        let cursors = run_paths
+            .into_iter()
+            .map(RunCursor::open)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            cursors,
+            prev: None,
+            key_columns,
+            side_label,
+            _run_dir: run_dir,
+        })
+    }
+
+    fn next(&mut self) -> Result<Option<SpillRecord>, EngineError> {
+        let mut min_idx: Option<usize> = None;
+        for (idx, cursor) in self.cursors.iter().enumerate() {
+            let Some(record) = &cursor.peeked else {
+                continue;
+            };
+            let is_smaller = match min_idx {
+                None => true,
+                Some(current) => record.key < self.cursors[current].peeked.as_ref().unwrap().key,
+            };
+            if is_smaller {
+                min_idx = Some(idx);
+            }
+        }
+
+        let Some(idx) = min_idx else {
+            return Ok(None);
+        };
+
+        let record = self.cursors[idx].peeked.take().expect("checked above");
+        self.cursors[idx].advance()?;
+
+        if let Some(prev) = &self.prev {
+            if prev.key == record.key {
+                return Err(diff_error(
+                    "duplicate_key",
+                    format!(
+                        "Duplicate key in {}: {} (rows {} and {})",
+                        self.side_label,
+                        key_object(self.key_columns, &record.key),
+                        prev.row_index,
+                        record.row_index
+                    ),
+                ));
+            }
+        }
+
+        self.prev = Some(record.clone());
+        Ok(Some(record))
+    }
+}
+
+/// Alternative to `diff_partitioned_from_manifest` that externally sorts each
+/// partition's two sides and merge-joins them with a two-pointer sweep,
+/// rather than hashing a whole partition into memory. At most
+/// `EXTERNAL_SORT_CHUNK_ROWS` parsed records are held per sort chunk, but each
+/// partition's spilled text is read into memory whole before it is sorted.
+pub fn diff_partitioned_from_manifest_sorted(
+    manifest: &PartitionManifest,
+    options: &DiffOptions,
+) -> Result<Vec<Value>, EngineError> {
+    let mut events: Vec<Value> = Vec::new();
+    events.push(json!({
+        "type": "schema",
+        "columns_a": &manifest.columns_a,
+        "columns_b": &manifest.columns_b
+    }));
+
+    let mut rows_total_compared = 0u64;
+    let mut rows_added = 0u64;
+    let mut rows_removed = 0u64;
+    let mut rows_changed = 0u64;
+    let mut rows_unchanged = 0u64;
+
+    for partition_id in 0..manifest.spill.partitions() {
+        let mut stream_a = SortedPartitionStream::open(
+            manifest.spill.as_ref(),
+            "a",
+            partition_id,
+            &options.key_columns,
+            "A",
+        )?;
+        let mut stream_b = SortedPartitionStream::open(
+            manifest.spill.as_ref(),
+            "b",
+            partition_id,
+            &options.key_columns,
+            "B",
+        )?;
+
+        let mut next_a = stream_a.next()?;
+        let mut next_b = stream_b.next()?;
+
+        loop {
+            match (next_a.take(), next_b.take()) {
+                (None, None) => break,
+                (Some(record_a), None) => {
+                    rows_removed += 1;
+                    events.push(json!({
+                        "type": "removed",
+                        "key": key_object(&options.key_columns, &record_a.key),
+                        "row": row_to_value(&record_a.row)
+                    }));
+                    next_a = stream_a.next()?;
+                }
+                (None, Some(record_b)) => {
+                    rows_added += 1;
+                    events.push(json!({
+                        "type": "added",
+                        "key": key_object(&options.key_columns, &record_b.key),
+                        "row": row_to_value(&record_b.row)
+                    }));
+                    next_b = stream_b.next()?;
+                }
+                (Some(record_a), Some(record_b)) => match record_a.key.cmp(&record_b.key) {
+                    std::cmp::Ordering::Less => {
+                        rows_removed += 1;
+                        events.push(json!({
+                            "type": "removed",
+                            "key": key_object(&options.key_columns, &record_a.key),
+                            "row": row_to_value(&record_a.row)
+                        }));
+                        next_a = stream_a.next()?;
+                        next_b = Some(record_b);
+                    }
+                    std::cmp::Ordering::Greater => {
+                        rows_added += 1;
+                        events.push(json!({
+                            "type": "added",
+                            "key": key_object(&options.key_columns, &record_b.key),
+                            "row": row_to_value(&record_b.row)
+                        }));
+                        next_b = stream_b.next()?;
+                        next_a = Some(record_a);
+                    }
+                    std::cmp::Ordering::Equal => {
+                        rows_total_compared += 1;
+                        let key_obj = key_object(&options.key_columns, &record_a.key);
+                        if apply_matched_row(
+                            &mut events,
+                            key_obj,
+                            &record_a,
+                            &record_b,
+                            &manifest.compare_columns,
+                            options,
+                        ) {
+                            rows_changed += 1;
+                        } else {
+                            rows_unchanged += 1;
+                        }
+                        next_a = stream_a.next()?;
+                        next_b = stream_b.next()?;
+                    }
+                },
+            }
+        }
+    }
+
+    events.push(json!({
+        "type": "stats",
+        "rows_total_compared": rows_total_compared,
+        "rows_added": rows_added,
+        "rows_removed": rows_removed,
+        "rows_changed": rows_changed,
+        "rows_unchanged": rows_unchanged,
+        "bytes_spilled": manifest.spill.bytes_spilled()
+    }));
+    Ok(events)
+}
+
+/// Which rows a key-based join should produce, in the usual relational
+/// sense: `Inner` keeps only matched keys, the outer variants additionally
+/// keep one side's unmatched keys (null-filling the other side's columns),
+/// and the anti-join variants keep *only* one side's unmatched keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinMode {
+    Inner,
+    LeftOuter,
+    RightOuter,
+    FullOuter,
+    LeftAnti,
+    RightAnti,
+}
+
+/// Maps each side's non-key columns onto the merged row's output column
+/// names, suffixing with `_a`/`_b` wherever A and B share a non-key column
+/// name so the merged row stays unambiguous.
+struct JoinColumnPlan {
+    merged_columns: Vec<String>,
+    a_mappings: Vec<(String, String)>,
+    b_mappings: Vec<(String, String)>,
+}
+
+fn plan_join_columns(
+    key_columns: &[String],
+    columns_a: &[String],
+    columns_b: &[String],
+) -> JoinColumnPlan {
+    let key_set: HashSet<&String> = key_columns.iter().collect();
+    let non_key_a: Vec<&String> = columns_a
+        .iter()
+        .filter(|col| !key_set.contains(col))
+        .collect();
+    let non_key_b: Vec<&String> = columns_b
+        .iter()
+        .filter(|col| !key_set.contains(col))
+        .collect();
+    let a_names: HashSet<&String> = non_key_a.iter().copied().collect();
+    let b_names: HashSet<&String> = non_key_b.iter().copied().collect();
+
+    let mut merged_columns = key_columns.to_vec();
+
+    let mut a_mappings = Vec::with_capacity(non_key_a.len());
+    for column in &non_key_a {
+        let output = if b_names.contains(column) {
+            format!("{column}_a")
+        } else {
+            (*column).clone()
+        };
+        merged_columns.push(output.clone());
+        a_mappings.push(((*column).clone(), output));
+    }
+
+    let mut b_mappings = Vec::with_capacity(non_key_b.len());
+    for column in &non_key_b {
+        let output = if a_names.contains(column) {
+            format!("{column}_b")
+        } else {
+            (*column).clone()
+        };
+        merged_columns.push(output.clone());
+        b_mappings.push(((*column).clone(), output));
+    }
+
+    JoinColumnPlan {
+        merged_columns,
+        a_mappings,
+        b_mappings,
+    }
 }
 
-pub fn diff_partitioned_from_manifest(
+fn build_joined_row(
+    plan: &JoinColumnPlan,
+    key_columns: &[String],
+    key: &[String],
+    record_a: Option<&SpillRecord>,
+    record_b: Option<&SpillRecord>,
+) -> Value {
+    let mut row = Map::new();
+    for (idx, column) in key_columns.iter().enumerate() {
+        row.insert(column.clone(), json!(key[idx]));
+    }
+    for (source, output) in &plan.a_mappings {
+        let value = record_a.and_then(|record| record.row.get(source)).cloned();
+        row.insert(
+            output.clone(),
+            value.map(Value::String).unwrap_or(Value::Null),
+        );
+    }
+    for (source, output) in &plan.b_mappings {
+        let value = record_b.and_then(|record| record.row.get(source)).cloned();
+        row.insert(
+            output.clone(),
+            value.map(Value::String).unwrap_or(Value::Null),
+        );
+    }
+    Value::Object(row)
+}
+
+/// Relational join over the same partitioned-by-key spill that
+/// `diff_partitioned_from_manifest` diffs, reusing `index_spill_records` to
+/// resolve matches per partition. Emits `row` events carrying the combined
+/// column set rather than diff events.
+pub fn join_partitioned_from_manifest(
     manifest: &PartitionManifest,
     options: &DiffOptions,
+    mode: JoinMode,
 ) -> Result<Vec<Value>, EngineError> {
+    let plan = plan_join_columns(
+        &options.key_columns,
+        &manifest.columns_a,
+        &manifest.columns_b,
+    );
+
     let mut events: Vec<Value> = Vec::new();
     events.push(json!({
         "type": "schema",
-        "columns_a": &manifest.columns_a,
-        "columns_b": &manifest.columns_b
+        "columns": &plan.merged_columns
     }));
 
-    let mut rows_total_compared = 0u64;
-    let mut rows_added = 0u64;
-    let mut rows_removed = 0u64;
-    let mut rows_changed = 0u64;
-    let mut rows_unchanged = 0u64;
+    let mut rows_matched = 0u64;
+    let mut rows_left_only = 0u64;
+    let mut rows_right_only = 0u64;
 
     for partition_id in 0..manifest.spill.partitions() {
         let indexed_a = index_spill_records(
-            read_spill_records(&manifest.spill, "a", partition_id)?,
+            read_spill_records(manifest.spill.as_ref(), "a", partition_id)?,
             &options.key_columns,
             "A",
         )?;
         let indexed_b = index_spill_records(
-            read_spill_records(&manifest.spill, "b", partition_id)?,
+            read_spill_records(manifest.spill.as_ref(), "b", partition_id)?,
             &options.key_columns,
             "B",
         )?;
@@ -617,76 +2456,57 @@ pub fn diff_partitioned_from_manifest(
             let in_a = indexed_a.get(&key);
             let in_b = indexed_b.get(&key);
 
-            match (in_a, in_b) {
-                (None, Some(record_b)) => {
-                    rows_added += 1;
+            match (in_a, in_b, mode) {
+                (
+                    Some(record_a),
+                    Some(record_b),
+                    JoinMode::Inner
+                    | JoinMode::LeftOuter
+                    | JoinMode::RightOuter
+                    | JoinMode::FullOuter,
+                ) => {
+                    rows_matched += 1;
                     events.push(json!({
-                        "type": "added",
+                        "type": "row",
                         "key": key_obj,
-                        "row": row_to_value(&record_b.row)
+                        "row": build_joined_row(&plan, &options.key_columns, &key, Some(record_a), Some(record_b))
                     }));
                 }
-                (Some(record_a), None) => {
-                    rows_removed += 1;
+                (
+                    Some(record_a),
+                    None,
+                    JoinMode::LeftOuter | JoinMode::FullOuter | JoinMode::LeftAnti,
+                ) => {
+                    rows_left_only += 1;
                     events.push(json!({
-                        "type": "removed",
+                        "type": "row",
                         "key": key_obj,
-                        "row": row_to_value(&record_a.row)
+                        "row": build_joined_row(&plan, &options.key_columns, &key, Some(record_a), None)
                     }));
                 }
-                (Some(record_a), Some(record_b)) => {
-                    rows_total_compared += 1;
-                    let changed_columns: Vec<String> = manifest
-                        .compare_columns
-                        .iter()
-                        .filter(|column| record_a.row.get(*column) != record_b.row.get(*column))
-                        .cloned()
-                        .collect();
-
-                    if changed_columns.is_empty() {
-                        rows_unchanged += 1;
-                        if options.emit_unchanged {
-                            events.push(json!({
-                                "type": "unchanged",
-                                "key": key_obj,
-                                "row": row_to_value(&record_a.row)
-                            }));
-                        }
-                    } else {
-                        rows_changed += 1;
-                        let mut delta = Map::new();
-                        for column in &changed_columns {
-                            delta.insert(
-                                column.clone(),
-                                json!({
-                                    "from": record_a.row.get(column).cloned().unwrap_or_default(),
-                                    "to": record_b.row.get(column).cloned().unwrap_or_default()
-                                }),
-                            );
-                        }
-
-                        events.push(json!({
-                            "type": "changed",
-                            "key": key_obj,
-                            "changed": changed_columns,
-                            "before": row_to_value(&record_a.row),
-                            "after": row_to_value(&record_b.row),
-                            "delta": Value::Object(delta)
-                        }));
-                    }
+                (
+                    None,
+                    Some(record_b),
+                    JoinMode::RightOuter | JoinMode::FullOuter | JoinMode::RightAnti,
+                ) => {
+                    rows_right_only += 1;
+                    events.push(json!({
+                        "type": "row",
+                        "key": key_obj,
+                        "row": build_joined_row(&plan, &options.key_columns, &key, None, Some(record_b))
+                    }));
                 }
-                (None, None) => {}
+                _ => {}
             }
         }
     }
 
     events.push(json!({
         "type": "stats",
-        "rows_total_compared": rows_total_compared,
-        "rows_added": rows_added,
-        "rows_removed": rows_removed,
-        "rows_changed": rows_changed,
-        "rows_unchanged": rows_unchanged
+        "rows_matched": rows_matched,
+        "rows_left_only": rows_left_only,
+        "rows_right_only": rows_right_only,
+        "bytes_spilled": manifest.spill.bytes_spilled()
     }));
     Ok(events)
 }
@@ -722,15 +2542,32 @@ pub fn run_keyed_to_sink(
     )
 }
 
-pub fn run_keyed_to_sink_with_config(
-    a_path: &Path,
-    b_path: &Path,
+/// Runs the partitioned diff implementation selected by
+/// `run_config.merge_strategy` over an already-built manifest.
+fn diff_events_for_manifest(
+    manifest: &PartitionManifest,
     options: &DiffOptions,
     run_config: &EngineRunConfig,
+) -> Result<Vec<Value>, EngineError> {
+    match run_config.merge_strategy {
+        MergeStrategy::Hashed => diff_partitioned_from_manifest_with_budget(
+            manifest,
+            options,
+            run_config.partition_memory_budget,
+        ),
+        MergeStrategy::Sorted => diff_partitioned_from_manifest_sorted(manifest, options),
+    }
+}
+
+/// Streams `events` through `sink` one at a time, emitting progress events
+/// at `run_config.progress_interval_events` when `run_config.emit_progress`
+/// is set. Shared by every `*_to_sink` entry point in this module.
+fn emit_events_with_progress(
+    events: Vec<Value>,
+    run_config: &EngineRunConfig,
     cancel_check: &dyn CancelCheck,
     sink: &mut dyn EventSink,
 ) -> Result<(), EngineError> {
-    let events = diff_csv_files(a_path, b_path, options).map_err(EngineError::Diff)?;
     let total_events = events.len();
     let interval = run_config.progress_interval_events.max(1);
 
@@ -755,6 +2592,385 @@ pub fn run_keyed_to_sink_with_config(
     Ok(())
 }
 
+/// Adapts a `diff_csv_files_streaming`/`diff_csv_bytes_streaming` call onto
+/// an `EventSink`. The core-level callback is typed over `DiffError` and has
+/// no notion of cancellation or sink failures, so those are stashed here and
+/// re-raised as the real `EngineError` once `run` returns its (necessarily
+/// `DiffError`-typed) result.
+fn stream_events(
+    run: impl FnOnce(&mut dyn FnMut(Value) -> Result<(), DiffError>) -> Result<(), DiffError>,
+    cancel_check: &dyn CancelCheck,
+    sink: &mut dyn EventSink,
+) -> Result<(), EngineError> {
+    let mut failure: Option<EngineError> = None;
+    let mut on_event = |event: Value| -> Result<(), DiffError> {
+        if cancel_check.cancelled() {
+            failure = Some(EngineError::Cancelled);
+            return Err(DiffError::new("cancelled", "Operation cancelled"));
+        }
+        if let Err(message) = sink.on_event(&event) {
+            failure = Some(EngineError::Sink(message.clone()));
+            return Err(DiffError::new("sink_error", message));
+        }
+        Ok(())
+    };
+    let result = run(&mut on_event);
+    match failure {
+        Some(failure) => Err(failure),
+        None => result.map_err(EngineError::Diff),
+    }
+}
+
+pub fn run_keyed_to_sink_with_config(
+    a_path: &Path,
+    b_path: &Path,
+    options: &DiffOptions,
+    run_config: &EngineRunConfig,
+    cancel_check: &dyn CancelCheck,
+    sink: &mut dyn EventSink,
+) -> Result<(), EngineError> {
+    if run_config.streaming && run_config.partition_count.is_none() {
+        return stream_events(
+            |on_event| diff_csv_files_streaming(a_path, b_path, options, on_event),
+            cancel_check,
+            sink,
+        );
+    }
+
+    let events = match run_config.partition_count {
+        Some(partitions) if !options.key_columns.is_empty() => {
+            let manifest = partition_inputs_to_spill(
+                a_path,
+                b_path,
+                options,
+                partitions,
+                run_config.spill_policy,
+            )?;
+            diff_events_for_manifest(&manifest, options, run_config)?
+        }
+        _ => diff_csv_files(a_path, b_path, options).map_err(EngineError::Diff)?,
+    };
+    emit_events_with_progress(events, run_config, cancel_check, sink)
+}
+
+/// Same as `run_keyed_to_sink_with_config`, but over an already-partitioned
+/// manifest — the entry point for diffing sources other than CSV, since
+/// `partition_sources_to_spill_with_store` builds a `PartitionManifest` from
+/// any two `RecordSource`s.
+pub fn run_partitioned_manifest_to_sink(
+    manifest: &PartitionManifest,
+    options: &DiffOptions,
+    run_config: &EngineRunConfig,
+    cancel_check: &dyn CancelCheck,
+    sink: &mut dyn EventSink,
+) -> Result<(), EngineError> {
+    let events = diff_events_for_manifest(manifest, options, run_config)?;
+    emit_events_with_progress(events, run_config, cancel_check, sink)
+}
+
+/// Streams a `join_partitioned_from_manifest` result through an `EventSink`
+/// one event at a time, mirroring `run_keyed_to_sink`'s shape for the diff
+/// path.
+pub fn run_join_to_sink(
+    manifest: &PartitionManifest,
+    options: &DiffOptions,
+    mode: JoinMode,
+    cancel_check: &dyn CancelCheck,
+    sink: &mut dyn EventSink,
+) -> Result<(), EngineError> {
+    let events = join_partitioned_from_manifest(manifest, options, mode)?;
+    for event in events {
+        if cancel_check.cancelled() {
+            return Err(EngineError::Cancelled);
+        }
+        sink.on_event(&event).map_err(EngineError::Sink)?;
+    }
+    Ok(())
+}
+
+/// What a `MergeSolver` decides for one key's base/A/B rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    TakeA,
+    TakeB,
+    Merged(BTreeMap<String, String>),
+    Conflict,
+}
+
+/// Pluggable arbitration for a three-way merge. Invoked once per key whose
+/// base/A/B rows aren't a clean auto-merge (i.e. the key itself was added,
+/// deleted, or modified differently on both sides relative to `base`).
+pub trait MergeSolver {
+    fn resolve(
+        &self,
+        key: &[String],
+        base: Option<&BTreeMap<String, String>>,
+        a: Option<&BTreeMap<String, String>>,
+        b: Option<&BTreeMap<String, String>>,
+    ) -> Resolution;
+}
+
+/// Default `MergeSolver`: takes whichever side changed a row (or left it
+/// alone), merges column-by-column when each side touched disjoint columns,
+/// and falls back to `Conflict` only where base/A/B can't be reconciled
+/// without picking a side.
+pub struct AutoMergeSolver;
+
+impl MergeSolver for AutoMergeSolver {
+    fn resolve(
+        &self,
+        _key: &[String],
+        base: Option<&BTreeMap<String, String>>,
+        a: Option<&BTreeMap<String, String>>,
+        b: Option<&BTreeMap<String, String>>,
+    ) -> Resolution {
+        match (base, a, b) {
+            (None, Some(_row_a), None) => Resolution::TakeA,
+            (None, None, Some(_row_b)) => Resolution::TakeB,
+            (None, Some(row_a), Some(row_b)) => {
+                if row_a == row_b {
+                    Resolution::Merged(row_a.clone())
+                } else {
+                    Resolution::Conflict
+                }
+            }
+            (Some(row_base), None, Some(row_b)) => {
+                if row_b == row_base {
+                    Resolution::TakeA
+                } else {
+                    Resolution::Conflict
+                }
+            }
+            (Some(row_base), Some(row_a), None) => {
+                if row_a == row_base {
+                    Resolution::TakeB
+                } else {
+                    Resolution::Conflict
+                }
+            }
+            (Some(row_base), Some(row_a), Some(row_b)) => {
+                let columns: HashSet<&String> = row_base
+                    .keys()
+                    .chain(row_a.keys())
+                    .chain(row_b.keys())
+                    .collect();
+
+                let mut merged = row_base.clone();
+                let mut conflicted = false;
+                for column in columns {
+                    let base_value = row_base.get(column);
+                    let a_value = row_a.get(column);
+                    let b_value = row_b.get(column);
+                    let a_changed = a_value != base_value;
+                    let b_changed = b_value != base_value;
+
+                    match (a_changed, b_changed) {
+                        (false, false) => {}
+                        (true, false) => {
+                            set_or_remove(&mut merged, column, a_value);
+                        }
+                        (false, true) => {
+                            set_or_remove(&mut merged, column, b_value);
+                        }
+                        (true, true) => {
+                            if a_value == b_value {
+                                set_or_remove(&mut merged, column, a_value);
+                            } else {
+                                conflicted = true;
+                            }
+                        }
+                    }
+                }
+
+                if conflicted {
+                    Resolution::Conflict
+                } else {
+                    Resolution::Merged(merged)
+                }
+            }
+            _ => Resolution::Conflict,
+        }
+    }
+}
+
+fn set_or_remove(row: &mut BTreeMap<String, String>, column: &str, value: Option<&String>) {
+    match value {
+        Some(value) => {
+            row.insert(column.to_string(), value.clone());
+        }
+        None => {
+            row.remove(column);
+        }
+    }
+}
+
+fn conflicting_columns(
+    compare_columns: &[String],
+    record_base: Option<&SpillRecord>,
+    record_a: Option<&SpillRecord>,
+    record_b: Option<&SpillRecord>,
+) -> Vec<Value> {
+    let mut conflicts = Vec::new();
+    for column in compare_columns {
+        let base_value = record_base
+            .and_then(|record| record.row.get(column))
+            .cloned();
+        let a_value = record_a.and_then(|record| record.row.get(column)).cloned();
+        let b_value = record_b.and_then(|record| record.row.get(column)).cloned();
+        if a_value != base_value && b_value != base_value && a_value != b_value {
+            conflicts.push(json!({
+                "column": column,
+                "base": base_value,
+                "a": a_value,
+                "b": b_value
+            }));
+        }
+    }
+    conflicts
+}
+
+/// Three-way merge over a `PartitionManifest3`, mirroring
+/// `diff_partitioned_from_manifest`'s per-partition indexed-match shape but
+/// with a third `base` side and a pluggable `MergeSolver` standing in for
+/// `apply_matched_row`. Keys deleted identically on both A and B are dropped
+/// silently; everything else is either auto-merged or handed to `solver`.
+pub fn merge_partitioned_from_manifest(
+    manifest: &PartitionManifest3,
+    options: &DiffOptions,
+    solver: &dyn MergeSolver,
+) -> Result<Vec<Value>, EngineError> {
+    let mut events: Vec<Value> = Vec::new();
+    events.push(json!({
+        "type": "schema",
+        "columns_base": &manifest.columns_base,
+        "columns_a": &manifest.columns_a,
+        "columns_b": &manifest.columns_b
+    }));
+
+    let mut rows_merged = 0u64;
+    let mut rows_conflicted = 0u64;
+    let mut rows_removed = 0u64;
+
+    for partition_id in 0..manifest.spill.partitions() {
+        let indexed_base = index_spill_records(
+            read_spill_records(manifest.spill.as_ref(), "base", partition_id)?,
+            &options.key_columns,
+            "Base",
+        )?;
+        let indexed_a = index_spill_records(
+            read_spill_records(manifest.spill.as_ref(), "a", partition_id)?,
+            &options.key_columns,
+            "A",
+        )?;
+        let indexed_b = index_spill_records(
+            read_spill_records(manifest.spill.as_ref(), "b", partition_id)?,
+            &options.key_columns,
+            "B",
+        )?;
+
+        let mut all_keys: Vec<Vec<String>> = indexed_base
+            .keys()
+            .chain(indexed_a.keys())
+            .chain(indexed_b.keys())
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        all_keys.sort();
+
+        for key in all_keys {
+            let record_base = indexed_base.get(&key);
+            let record_a = indexed_a.get(&key);
+            let record_b = indexed_b.get(&key);
+
+            if record_a.is_none() && record_b.is_none() {
+                // Deleted on both sides (or never present on either) — nothing to merge.
+                rows_removed += 1;
+                continue;
+            }
+
+            let key_obj = key_object(&options.key_columns, &key);
+            let resolution = solver.resolve(
+                &key,
+                record_base.map(|record| &record.row),
+                record_a.map(|record| &record.row),
+                record_b.map(|record| &record.row),
+            );
+
+            match resolution {
+                Resolution::TakeA => match record_a {
+                    Some(record) => {
+                        rows_merged += 1;
+                        events.push(json!({
+                            "type": "merged",
+                            "key": key_obj,
+                            "row": row_to_value(&record.row)
+                        }));
+                    }
+                    // A deleted a row that B left as it was in base.
+                    None => rows_removed += 1,
+                },
+                Resolution::TakeB => match record_b {
+                    Some(record) => {
+                        rows_merged += 1;
+                        events.push(json!({
+                            "type": "merged",
+                            "key": key_obj,
+                            "row": row_to_value(&record.row)
+                        }));
+                    }
+                    // B deleted a row that A left as it was in base.
+                    None => rows_removed += 1,
+                },
+                Resolution::Merged(row) => {
+                    rows_merged += 1;
+                    events.push(json!({
+                        "type": "merged",
+                        "key": key_obj,
+                        "row": row_to_value(&row)
+                    }));
+                }
+                Resolution::Conflict => {
+                    rows_conflicted += 1;
+                    events.push(json!({
+                        "type": "conflict",
+                        "key": key_obj,
+                        "conflicts": conflicting_columns(&manifest.compare_columns, record_base, record_a, record_b)
+                    }));
+                }
+            }
+        }
+    }
+
+    events.push(json!({
+        "type": "stats",
+        "rows_merged": rows_merged,
+        "rows_conflicted": rows_conflicted,
+        "rows_removed": rows_removed,
+        "bytes_spilled": manifest.spill.bytes_spilled()
+    }));
+    Ok(events)
+}
+
+/// Streams a `merge_partitioned_from_manifest` result through an
+/// `EventSink`, mirroring `run_join_to_sink`'s shape for the join path.
+pub fn run_merge_to_sink(
+    manifest: &PartitionManifest3,
+    options: &DiffOptions,
+    solver: &dyn MergeSolver,
+    cancel_check: &dyn CancelCheck,
+    sink: &mut dyn EventSink,
+) -> Result<(), EngineError> {
+    let events = merge_partitioned_from_manifest(manifest, options, solver)?;
+    for event in events {
+        if cancel_check.cancelled() {
+            return Err(EngineError::Cancelled);
+        }
+        sink.on_event(&event).map_err(EngineError::Sink)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -790,6 +3006,19 @@ mod tests {
         path
     }
 
+    fn write_ndjson(name: &str, content: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock drift")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!(
+            "diffly-engine-{name}-{}-{nanos}.ndjson",
+            std::process::id()
+        ));
+        fs::write(&path, content).expect("failed to write ndjson fixture");
+        path
+    }
+
     fn default_options() -> DiffOptions {
         DiffOptions {
             key_columns: vec!["id".to_string()],
@@ -806,6 +3035,7 @@ mod tests {
         let run_config = EngineRunConfig {
             emit_progress: true,
             progress_interval_events: 1,
+            ..EngineRunConfig::default()
         };
 
         run_keyed_to_sink_with_config(
@@ -848,7 +3078,7 @@ mod tests {
 
     #[test]
     fn spills_records_into_partition_files() {
-        let spill = TempDirSpill::new(8).expect("spill should initialize");
+        let spill = TempDirSpill::new(8, SpillPolicy::default()).expect("spill should initialize");
         let key = vec!["123".to_string(), "eu".to_string()];
         let partition = spill_json_record(
             &spill,
@@ -865,13 +3095,74 @@ mod tests {
         assert!(spill.root_path().exists());
     }
 
+    #[test]
+    fn memory_spill_round_trips_partitioned_diff_without_touching_disk() {
+        let a = write_csv("memory-spill-a", "id,name\n1,Alice\n2,Bob\n");
+        let b = write_csv("memory-spill-b", "id,name\n1,Alicia\n3,Cara\n");
+
+        let spill: Box<dyn SpillStore> = Box::new(MemorySpill::new(4).expect("spill should init"));
+        let manifest = partition_inputs_to_spill_with_store(&a, &b, &default_options(), spill)
+            .expect("partitioning should succeed");
+        assert_eq!(manifest.spill.bytes_spilled(), 0);
+
+        let events =
+            diff_partitioned_from_manifest(&manifest, &default_options()).expect("diff succeeds");
+        let stats = events.last().expect("stats should be present");
+        assert_eq!(stats.get("rows_added").and_then(Value::as_u64), Some(1));
+        assert_eq!(stats.get("rows_removed").and_then(Value::as_u64), Some(1));
+        assert_eq!(stats.get("rows_changed").and_then(Value::as_u64), Some(1));
+        assert_eq!(stats.get("bytes_spilled").and_then(Value::as_u64), Some(0));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn small_buffers_never_touch_disk() {
+        let spill = TempDirSpill::new(4, SpillPolicy::default()).expect("spill should initialize");
+        spill
+            .append_line("a", 0, "{\"id\":\"1\"}")
+            .expect("append should succeed");
+
+        let path = spill.partition_path("a", 0).expect("path should resolve");
+        assert!(!path.exists());
+        assert_eq!(spill.bytes_spilled(), 0);
+
+        let contents = spill
+            .read_partition("a", 0)
+            .expect("in-memory partition should be readable");
+        assert!(contents.contains("\"id\":\"1\""));
+    }
+
+    #[test]
+    fn buffers_flush_once_spill_bytes_limit_is_exceeded() {
+        let policy = SpillPolicy {
+            spill_bytes_limit: 16,
+            ..SpillPolicy::default()
+        };
+        let spill = TempDirSpill::new(4, policy).expect("spill should initialize");
+        spill
+            .append_line("a", 0, "{\"id\":\"1234567890\"}")
+            .expect("append should succeed");
+
+        let path = spill.partition_path("a", 0).expect("path should resolve");
+        assert!(path.exists());
+        assert!(spill.bytes_spilled() > 0);
+
+        let contents = spill
+            .read_partition("a", 0)
+            .expect("flushed partition should be readable");
+        assert!(contents.contains("\"id\":\"1234567890\""));
+    }
+
     #[test]
     fn partitions_inputs_to_spill_with_counts() {
         let a = write_csv("partition-a", "id,name\n1,Alice\n2,Bob\n");
         let b = write_csv("partition-b", "id,name\n1,Alicia\n3,Cara\n");
 
-        let manifest = partition_inputs_to_spill(&a, &b, &default_options(), 4)
-            .expect("partitioning should succeed");
+        let manifest =
+            partition_inputs_to_spill(&a, &b, &default_options(), 4, SpillPolicy::default())
+                .expect("partitioning should succeed");
 
         assert_eq!(
             manifest.columns_a,
@@ -893,7 +3184,7 @@ mod tests {
         let mut observed_records = 0usize;
         for partition_id in 0..manifest.spill.partitions() {
             if manifest.partition_rows_a[partition_id] > 0 {
-                let records = read_spill_records(&manifest.spill, "a", partition_id)
+                let records = read_spill_records(manifest.spill.as_ref(), "a", partition_id)
                     .expect("partition A should be decodable");
                 observed_records += records.len();
                 for record in records {
@@ -911,7 +3202,7 @@ mod tests {
 
     #[test]
     fn read_spill_records_missing_partition_returns_empty() {
-        let spill = TempDirSpill::new(2).expect("spill should initialize");
+        let spill = TempDirSpill::new(2, SpillPolicy::default()).expect("spill should initialize");
         let records = read_spill_records(&spill, "a", 1).expect("read should succeed");
         assert!(records.is_empty());
     }
@@ -921,8 +3212,9 @@ mod tests {
         let a = write_csv("partitioned-diff-a", "id,name\n1,Alice\n2,Bob\n");
         let b = write_csv("partitioned-diff-b", "id,name\n1,Alicia\n3,Cara\n");
 
-        let manifest = partition_inputs_to_spill(&a, &b, &default_options(), 4)
-            .expect("partitioning should succeed");
+        let manifest =
+            partition_inputs_to_spill(&a, &b, &default_options(), 4, SpillPolicy::default())
+                .expect("partitioning should succeed");
         let events =
             diff_partitioned_from_manifest(&manifest, &default_options()).expect("diff succeeds");
 
@@ -954,8 +3246,9 @@ mod tests {
         let a = write_csv("partitioned-dup-a", "id,name\n1,Alice\n1,Alicia\n");
         let b = write_csv("partitioned-dup-b", "id,name\n1,Alice\n");
 
-        let manifest = partition_inputs_to_spill(&a, &b, &default_options(), 4)
-            .expect("partitioning should succeed");
+        let manifest =
+            partition_inputs_to_spill(&a, &b, &default_options(), 4, SpillPolicy::default())
+                .expect("partitioning should succeed");
         let err = diff_partitioned_from_manifest(&manifest, &default_options())
             .expect_err("duplicate key should fail");
 
@@ -971,12 +3264,193 @@ mod tests {
         let _ = fs::remove_file(b);
     }
 
+    #[test]
+    fn partition_sources_to_spill_matches_the_csv_path_specific_entrypoint() {
+        let a = write_csv("sources-parity-a", "id,name\n1,Alice\n2,Bob\n");
+        let b = write_csv("sources-parity-b", "id,name\n1,Alicia\n3,Cara\n");
+
+        let via_paths =
+            partition_inputs_to_spill(&a, &b, &default_options(), 4, SpillPolicy::default())
+                .expect("path-based partitioning should succeed");
+
+        let source_a: Box<dyn RecordSource> = Box::new(
+            CsvRecordSource::open(&a, "A", &default_options()).expect("source should open"),
+        );
+        let source_b: Box<dyn RecordSource> = Box::new(
+            CsvRecordSource::open(&b, "B", &default_options()).expect("source should open"),
+        );
+        let spill: Box<dyn SpillStore> = Box::new(
+            TempDirSpill::new(4, SpillPolicy::default()).expect("spill should initialize"),
+        );
+        let via_sources =
+            partition_sources_to_spill_with_store(source_a, source_b, &default_options(), spill)
+                .expect("source-based partitioning should succeed");
+
+        assert_eq!(via_paths.columns_a, via_sources.columns_a);
+        assert_eq!(via_paths.row_count_a, via_sources.row_count_a);
+        assert_eq!(via_paths.row_count_b, via_sources.row_count_b);
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn diffs_a_csv_export_against_an_ndjson_snapshot_of_the_same_table() {
+        let a = write_csv("csv-vs-ndjson-a", "id,name\n1,Alice\n2,Bob\n");
+        let b = write_ndjson(
+            "csv-vs-ndjson-b",
+            "{\"id\":\"1\",\"name\":\"Alicia\"}\n{\"id\":\"3\",\"name\":\"Cara\"}\n",
+        );
+
+        let source_a: Box<dyn RecordSource> = Box::new(
+            CsvRecordSource::open(&a, "A", &default_options()).expect("csv source should open"),
+        );
+        let source_b: Box<dyn RecordSource> =
+            Box::new(NdjsonRecordSource::open(&b, "B").expect("ndjson source should open"));
+        let spill: Box<dyn SpillStore> = Box::new(
+            TempDirSpill::new(4, SpillPolicy::default()).expect("spill should initialize"),
+        );
+        let manifest =
+            partition_sources_to_spill_with_store(source_a, source_b, &default_options(), spill)
+                .expect("cross-format partitioning should succeed");
+        let events =
+            diff_partitioned_from_manifest(&manifest, &default_options()).expect("diff succeeds");
+
+        let types: Vec<&str> = events
+            .iter()
+            .filter_map(|event| event.get("type").and_then(Value::as_str))
+            .collect();
+        assert!(types.contains(&"changed"));
+        assert!(types.contains(&"added"));
+        assert!(types.contains(&"removed"));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn budgeted_diff_matches_unbudgeted_diff_after_recursive_repartition() {
+        let mut a_rows = String::from("id,name\n");
+        let mut b_rows = String::from("id,name\n");
+        for id in 0..64 {
+            a_rows.push_str(&format!("{id},Name{id}\n"));
+            if id == 10 {
+                b_rows.push_str(&format!("{id},Renamed{id}\n"));
+            } else if id % 7 != 0 {
+                b_rows.push_str(&format!("{id},Name{id}\n"));
+            }
+        }
+        let a = write_csv("budget-repartition-a", &a_rows);
+        let b = write_csv("budget-repartition-b", &b_rows);
+
+        let manifest =
+            partition_inputs_to_spill(&a, &b, &default_options(), 4, SpillPolicy::default())
+                .expect("partitioning should succeed");
+
+        let unbudgeted = diff_partitioned_from_manifest(&manifest, &default_options())
+            .expect("unbudgeted diff succeeds");
+        let budgeted = diff_partitioned_from_manifest_with_budget(&manifest, &default_options(), 3)
+            .expect("budgeted diff should recursively repartition and succeed");
+
+        assert_eq!(unbudgeted, budgeted);
+        assert_eq!(
+            budgeted
+                .last()
+                .and_then(|event| event.get("rows_changed"))
+                .and_then(Value::as_u64),
+            Some(1)
+        );
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn budgeted_diff_reports_duplicate_key_when_a_single_key_exceeds_the_budget() {
+        let a = write_csv(
+            "budget-unsplittable-a",
+            "id,name\n1,Alice\n1,Alicia\n1,Aliyah\n",
+        );
+        let b = write_csv("budget-unsplittable-b", "id,name\n1,Alice\n");
+
+        let manifest =
+            partition_inputs_to_spill(&a, &b, &default_options(), 4, SpillPolicy::default())
+                .expect("partitioning should succeed");
+        let err = diff_partitioned_from_manifest_with_budget(&manifest, &default_options(), 1)
+            .expect_err("duplicate key sharing every sub-partition should still fail");
+
+        match err {
+            EngineError::Diff(diff_err) => assert_eq!(diff_err.code, "duplicate_key"),
+            other => panic!("expected Diff error, got {other:?}"),
+        }
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn sorted_merge_join_matches_hash_based_diff() {
+        let a = write_csv("sorted-diff-a", "id,name\n1,Alice\n2,Bob\n");
+        let b = write_csv("sorted-diff-b", "id,name\n1,Alicia\n3,Cara\n");
+
+        let manifest =
+            partition_inputs_to_spill(&a, &b, &default_options(), 4, SpillPolicy::default())
+                .expect("partitioning should succeed");
+        let events = diff_partitioned_from_manifest_sorted(&manifest, &default_options())
+            .expect("diff succeeds");
+
+        let types: Vec<&str> = events
+            .iter()
+            .filter_map(|event| event.get("type").and_then(Value::as_str))
+            .collect();
+        assert!(types.contains(&"schema"));
+        assert!(types.contains(&"changed"));
+        assert!(types.contains(&"added"));
+        assert!(types.contains(&"removed"));
+        assert_eq!(types.last(), Some(&"stats"));
+
+        let stats = events.last().expect("stats should be present");
+        assert_eq!(
+            stats.get("rows_total_compared").and_then(Value::as_u64),
+            Some(1)
+        );
+        assert_eq!(stats.get("rows_added").and_then(Value::as_u64), Some(1));
+        assert_eq!(stats.get("rows_removed").and_then(Value::as_u64), Some(1));
+        assert_eq!(stats.get("rows_changed").and_then(Value::as_u64), Some(1));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    #[test]
+    fn sorted_merge_join_duplicate_key_preserves_row_indices() {
+        let a = write_csv("sorted-dup-a", "id,name\n1,Alice\n1,Alicia\n");
+        let b = write_csv("sorted-dup-b", "id,name\n1,Alice\n");
+
+        let manifest =
+            partition_inputs_to_spill(&a, &b, &default_options(), 4, SpillPolicy::default())
+                .expect("partitioning should succeed");
+        let err = diff_partitioned_from_manifest_sorted(&manifest, &default_options())
+            .expect_err("duplicate key should fail");
+
+        match err {
+            EngineError::Diff(diff_err) => {
+                assert_eq!(diff_err.code, "duplicate_key");
+                assert!(diff_err.message.contains("rows 2 and 3"));
+            }
+            other => panic!("expected Diff error, got {other:?}"),
+        }
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
     #[test]
     fn partitioning_missing_key_value_is_hard_error() {
         let a = write_csv("partition-missing-key-a", "id,name\n,Blank\n");
         let b = write_csv("partition-missing-key-b", "id,name\n1,Alice\n");
 
-        let err = partition_inputs_to_spill(&a, &b, &default_options(), 4)
+        let err = partition_inputs_to_spill(&a, &b, &default_options(), 4, SpillPolicy::default())
             .expect_err("expected missing_key_value");
 
         match err {
@@ -987,4 +3461,358 @@ mod tests {
         let _ = fs::remove_file(a);
         let _ = fs::remove_file(b);
     }
+
+    fn join_fixture_manifest() -> PartitionManifest {
+        let a = write_csv("join-a", "id,name,city\n1,Alice,NYC\n2,Bob,LA\n");
+        let b = write_csv("join-b", "id,name,state\n1,Alicia,NY\n3,Cara,TX\n");
+
+        let manifest = partition_inputs_to_spill_for_join(
+            &a,
+            &b,
+            &default_options(),
+            4,
+            SpillPolicy::default(),
+        )
+        .expect("partitioning should succeed");
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+        manifest
+    }
+
+    // Regression test for a bug where join partitioning reused the diff
+    // path's strict header-equality check: any join across CSVs with
+    // different non-key columns (the entire point of a join) failed with
+    // `header_mismatch` instead of running. `partition_inputs_to_spill_for_join`
+    // must accept this input even though `partition_inputs_to_spill` (the
+    // diff path) correctly still rejects it.
+    #[test]
+    fn join_allows_disjoint_non_key_schemas() {
+        let a = write_csv("join-disjoint-a", "id,name,city\n1,Alice,NYC\n");
+        let b = write_csv("join-disjoint-b", "id,name,state\n1,Alicia,NY\n");
+
+        let diff_result =
+            partition_inputs_to_spill(&a, &b, &default_options(), 4, SpillPolicy::default());
+        assert!(
+            diff_result.is_err(),
+            "the diff path should still require matching schemas"
+        );
+
+        let join_manifest = partition_inputs_to_spill_for_join(
+            &a,
+            &b,
+            &default_options(),
+            4,
+            SpillPolicy::default(),
+        )
+        .expect("join partitioning should accept disjoint non-key columns");
+        let events =
+            join_partitioned_from_manifest(&join_manifest, &default_options(), JoinMode::Inner)
+                .expect("join succeeds");
+        let rows = joined_rows(&events);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["row"]["name_a"], json!("Alice"));
+        assert_eq!(rows[0]["row"]["name_b"], json!("Alicia"));
+
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+    }
+
+    fn joined_rows(events: &[Value]) -> Vec<&Value> {
+        events
+            .iter()
+            .filter(|event| event.get("type").and_then(Value::as_str) == Some("row"))
+            .collect()
+    }
+
+    #[test]
+    fn inner_join_keeps_only_matched_keys_with_disambiguated_columns() {
+        let manifest = join_fixture_manifest();
+        let events = join_partitioned_from_manifest(&manifest, &default_options(), JoinMode::Inner)
+            .expect("join succeeds");
+
+        let schema = events.first().expect("schema event");
+        let columns: Vec<&str> = schema["columns"]
+            .as_array()
+            .expect("columns array")
+            .iter()
+            .filter_map(Value::as_str)
+            .collect();
+        assert_eq!(columns, vec!["id", "name_a", "city", "name_b", "state"]);
+
+        let rows = joined_rows(&events);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["row"]["name_a"], json!("Alice"));
+        assert_eq!(rows[0]["row"]["name_b"], json!("Alicia"));
+
+        let stats = events.last().expect("stats event");
+        assert_eq!(stats.get("rows_matched").and_then(Value::as_u64), Some(1));
+        assert_eq!(stats.get("rows_left_only").and_then(Value::as_u64), Some(0));
+        assert_eq!(
+            stats.get("rows_right_only").and_then(Value::as_u64),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn full_outer_join_null_fills_unmatched_side() {
+        let manifest = join_fixture_manifest();
+        let events =
+            join_partitioned_from_manifest(&manifest, &default_options(), JoinMode::FullOuter)
+                .expect("join succeeds");
+
+        let rows = joined_rows(&events);
+        assert_eq!(rows.len(), 3);
+
+        let by_id = |id: &str| -> &Value {
+            rows.iter()
+                .find(|row| row["key"]["id"] == json!(id))
+                .expect("row should be present")
+        };
+        assert_eq!(by_id("2")["row"]["name_b"], Value::Null);
+        assert_eq!(by_id("3")["row"]["name_a"], Value::Null);
+
+        let stats = events.last().expect("stats event");
+        assert_eq!(stats.get("rows_matched").and_then(Value::as_u64), Some(1));
+        assert_eq!(stats.get("rows_left_only").and_then(Value::as_u64), Some(1));
+        assert_eq!(
+            stats.get("rows_right_only").and_then(Value::as_u64),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn left_anti_join_keeps_only_unmatched_a_keys() {
+        let manifest = join_fixture_manifest();
+        let events =
+            join_partitioned_from_manifest(&manifest, &default_options(), JoinMode::LeftAnti)
+                .expect("join succeeds");
+
+        let rows = joined_rows(&events);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["key"]["id"], json!("2"));
+
+        let stats = events.last().expect("stats event");
+        assert_eq!(stats.get("rows_left_only").and_then(Value::as_u64), Some(1));
+        assert_eq!(
+            stats.get("rows_right_only").and_then(Value::as_u64),
+            Some(0)
+        );
+        assert_eq!(stats.get("rows_matched").and_then(Value::as_u64), Some(0));
+    }
+
+    #[test]
+    fn run_join_to_sink_streams_row_and_stats_events() {
+        let manifest = join_fixture_manifest();
+        let mut sink = CollectSink { events: Vec::new() };
+
+        run_join_to_sink(
+            &manifest,
+            &default_options(),
+            JoinMode::RightOuter,
+            &NeverCancel,
+            &mut sink,
+        )
+        .expect("join run should succeed");
+
+        assert_eq!(
+            sink.events
+                .first()
+                .and_then(|event| event.get("type"))
+                .and_then(Value::as_str),
+            Some("schema")
+        );
+        assert_eq!(
+            sink.events
+                .last()
+                .and_then(|event| event.get("type"))
+                .and_then(Value::as_str),
+            Some("stats")
+        );
+        assert_eq!(joined_rows(&sink.events).len(), 2);
+    }
+
+    fn merge_fixture_manifest() -> PartitionManifest3 {
+        let base = write_csv(
+            "merge-base",
+            "id,name,city\n1,Alice,NYC\n2,Bob,LA\n3,Cara,TX\n4,Dan,Chicago\n",
+        );
+        let a = write_csv(
+            "merge-a",
+            "id,name,city\n1,Alice,Boston\n2,Bob,LA\n4,Dan,Houston\n5,Eve,Miami\n",
+        );
+        let b = write_csv(
+            "merge-b",
+            "id,name,city\n1,Alicia,NYC\n2,Bob,LA\n4,Dan,Dallas\n",
+        );
+
+        let manifest = partition_inputs3_to_spill(
+            &base,
+            &a,
+            &b,
+            &default_options(),
+            4,
+            SpillPolicy::default(),
+        )
+        .expect("three-way partitioning should succeed");
+
+        let _ = fs::remove_file(base);
+        let _ = fs::remove_file(a);
+        let _ = fs::remove_file(b);
+        manifest
+    }
+
+    fn merged_rows(events: &[Value]) -> Vec<&Value> {
+        events
+            .iter()
+            .filter(|event| event.get("type").and_then(Value::as_str) == Some("merged"))
+            .collect()
+    }
+
+    fn conflict_events(events: &[Value]) -> Vec<&Value> {
+        events
+            .iter()
+            .filter(|event| event.get("type").and_then(Value::as_str) == Some("conflict"))
+            .collect()
+    }
+
+    #[test]
+    fn merge_auto_takes_disjoint_column_changes() {
+        let manifest = merge_fixture_manifest();
+        let events =
+            merge_partitioned_from_manifest(&manifest, &default_options(), &AutoMergeSolver)
+                .expect("merge succeeds");
+
+        let merged = merged_rows(&events);
+        let row_1 = merged
+            .iter()
+            .find(|event| event["key"]["id"] == json!("1"))
+            .expect("row 1 should auto-merge");
+        assert_eq!(row_1["row"]["city"], json!("Boston"));
+        assert_eq!(row_1["row"]["name"], json!("Alicia"));
+    }
+
+    #[test]
+    fn merge_conflicting_column_reports_base_a_b_values() {
+        let manifest = merge_fixture_manifest();
+        let events =
+            merge_partitioned_from_manifest(&manifest, &default_options(), &AutoMergeSolver)
+                .expect("merge succeeds");
+
+        let conflicts = conflict_events(&events);
+        assert_eq!(conflicts.len(), 1);
+        let conflict = conflicts[0];
+        assert_eq!(conflict["key"]["id"], json!("4"));
+        let columns = conflict["conflicts"].as_array().expect("conflicts array");
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0]["column"], json!("city"));
+        assert_eq!(columns[0]["base"], json!("Chicago"));
+        assert_eq!(columns[0]["a"], json!("Houston"));
+        assert_eq!(columns[0]["b"], json!("Dallas"));
+    }
+
+    #[test]
+    fn merge_drops_rows_removed_on_both_sides() {
+        let manifest = merge_fixture_manifest();
+        let events =
+            merge_partitioned_from_manifest(&manifest, &default_options(), &AutoMergeSolver)
+                .expect("merge succeeds");
+
+        assert!(merged_rows(&events)
+            .iter()
+            .all(|event| event["key"]["id"] != json!("3")));
+        assert!(conflict_events(&events)
+            .iter()
+            .all(|event| event["key"]["id"] != json!("3")));
+
+        let stats = events.last().expect("stats event");
+        assert_eq!(stats.get("rows_removed").and_then(Value::as_u64), Some(1));
+        assert_eq!(
+            stats.get("rows_conflicted").and_then(Value::as_u64),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn merge_auto_takes_one_sided_addition() {
+        let manifest = merge_fixture_manifest();
+        let events =
+            merge_partitioned_from_manifest(&manifest, &default_options(), &AutoMergeSolver)
+                .expect("merge succeeds");
+
+        let merged = merged_rows(&events);
+        let row_5 = merged
+            .iter()
+            .find(|event| event["key"]["id"] == json!("5"))
+            .expect("row added only in A should auto-merge");
+        assert_eq!(row_5["row"]["name"], json!("Eve"));
+    }
+
+    #[test]
+    fn run_merge_to_sink_streams_merged_and_conflict_events() {
+        let manifest = merge_fixture_manifest();
+        let mut sink = CollectSink { events: Vec::new() };
+
+        run_merge_to_sink(
+            &manifest,
+            &default_options(),
+            &AutoMergeSolver,
+            &NeverCancel,
+            &mut sink,
+        )
+        .expect("merge run should succeed");
+
+        assert_eq!(
+            sink.events
+                .first()
+                .and_then(|event| event.get("type"))
+                .and_then(Value::as_str),
+            Some("schema")
+        );
+        assert_eq!(
+            sink.events
+                .last()
+                .and_then(|event| event.get("type"))
+                .and_then(Value::as_str),
+            Some("stats")
+        );
+        assert_eq!(conflict_events(&sink.events).len(), 1);
+    }
+
+    #[test]
+    fn tee_sink_forwards_every_event_to_all_wrapped_sinks() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct RecordingSink {
+            log: Rc<RefCell<Vec<Value>>>,
+        }
+
+        impl EventSink for RecordingSink {
+            fn on_event(&mut self, event: &Value) -> Result<(), String> {
+                self.log.borrow_mut().push(event.clone());
+                Ok(())
+            }
+        }
+
+        let log_a = Rc::new(RefCell::new(Vec::new()));
+        let log_b = Rc::new(RefCell::new(Vec::new()));
+        let mut tee = TeeSink::new(vec![
+            Box::new(RecordingSink {
+                log: Rc::clone(&log_a),
+            }),
+            Box::new(RecordingSink {
+                log: Rc::clone(&log_b),
+            }),
+        ]);
+
+        tee.on_event(&json!({"type": "added"}))
+            .expect("forward should succeed");
+        tee.on_event(&json!({"type": "removed"}))
+            .expect("forward should succeed");
+
+        assert_eq!(log_a.borrow().len(), 2);
+        assert_eq!(log_b.borrow().len(), 2);
+        assert_eq!(log_a.borrow()[1]["type"], json!("removed"));
+    }
 }
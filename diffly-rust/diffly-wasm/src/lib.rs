@@ -1,13 +1,32 @@
-use diffly_core::{diff_csv_bytes, DiffOptions, HeaderMode};
+use diffly_core::{diff_csv_bytes, DiffOptions, HeaderMode, JoinSelection, OutputMode};
 use wasm_bindgen::prelude::*;
 
+/// Resolves an optional single-character wasm-bindgen argument to a CSV
+/// dialect byte, defaulting when the caller passes `undefined`/`null`.
+fn dialect_byte(value: Option<char>, default: u8) -> Result<u8, JsValue> {
+    match value {
+        Some(c) if c.is_ascii() => Ok(c as u8),
+        Some(c) => Err(JsValue::from_str(&format!(
+            "delimiter/quote must be a single ASCII character, got: {c}"
+        ))),
+        None => Ok(default),
+    }
+}
+
 #[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
 pub fn diff_csv_bytes_json(
     a_bytes: &[u8],
     b_bytes: &[u8],
     key_columns_csv: &str,
     header_mode: &str,
     emit_unchanged: bool,
+    delimiter: Option<char>,
+    quote: Option<char>,
+    lossless_bytes: bool,
+    ignore_row_order: bool,
+    output_mode: Option<String>,
+    join_selection: Option<String>,
 ) -> Result<String, JsValue> {
     let key_columns: Vec<String> = key_columns_csv
         .split(',')
@@ -18,12 +37,28 @@ pub fn diff_csv_bytes_json(
 
     let header_mode =
         HeaderMode::parse(header_mode).map_err(|err| JsValue::from_str(&err.message))?;
+    let output_mode = match output_mode {
+        Some(value) => OutputMode::parse(&value).map_err(|err| JsValue::from_str(&err.message))?,
+        None => OutputMode::default(),
+    };
+    let join_selection = match join_selection {
+        Some(value) => {
+            JoinSelection::parse(&value).map_err(|err| JsValue::from_str(&err.message))?
+        }
+        None => JoinSelection::default(),
+    };
 
     let options = DiffOptions {
         key_columns,
         header_mode,
         emit_unchanged,
-        ignore_row_order: false,
+        delimiter: dialect_byte(delimiter, b',')?,
+        quote: dialect_byte(quote, b'"')?,
+        lossless_bytes,
+        ignore_row_order,
+        output_mode,
+        join_selection,
+        ..DiffOptions::default()
     };
 
     let events = diff_csv_bytes(a_bytes, b_bytes, &options)